@@ -4,10 +4,9 @@ use bevy::{
     prelude::*,
 };
 use bevy_egui::{
-    egui::{Align2, Area, Grid, Window},
+    egui::{Align2, Area, Button, Grid, Window},
     EguiContext, EguiPlugin,
 };
-use derive_more::Display;
 use leafwing_input_manager::{prelude::*, user_input::InputKind};
 
 const UI_MARGIN: f32 = 10.0;
@@ -60,7 +59,11 @@ fn controls_window_system(
                 .min_col_width(ui.available_width() / COLUMNS_COUNT as f32 - window_width_margin)
                 .show(ui, |ui| {
                     for action in ControlAction::variants() {
-                        ui.label(action.to_string());
+                        let metadata = action.metadata();
+                        let label = ui.label(metadata.name);
+                        if let Some(description) = metadata.description {
+                            label.on_hover_text(description);
+                        }
                         let inputs = control_settings.input.get(action);
                         for index in 0..INPUT_VARIANTS {
                             let button_text = match inputs.get_at(index) {
@@ -77,7 +80,9 @@ fn controls_window_system(
                                 }
                                 _ => "Empty".to_string(),
                             };
-                            if ui.button(button_text).clicked() {
+                            let button =
+                                ui.add_enabled(metadata.rebindable, Button::new(button_text));
+                            if button.clicked() {
                                 commands.insert_resource(ActiveBinding::new(action, index));
                             }
                         }
@@ -119,77 +124,84 @@ fn binding_window_system(
         Some(active_binding) => active_binding,
         None => return,
     };
-    Window::new(format!("Binding \"{}\"", active_binding.action))
-        .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
-        .collapsible(false)
-        .resizable(false)
-        .show(egui.ctx_mut(), |ui| {
-            if let Some(conflict) = &active_binding.conflict {
-                ui.label(format!(
-                    "Input \"{}\" is already used by \"{}\"",
-                    conflict.input_button, conflict.action
-                ));
-                ui.horizontal(|ui| {
-                    if ui.button("Replace").clicked() {
-                        control_settings
-                            .input
-                            .remove(conflict.action, conflict.input_button);
-                        control_settings.input.insert_at(
-                            conflict.input_button,
-                            active_binding.action,
-                            active_binding.index,
-                        );
-                        commands.remove_resource::<ActiveBinding>();
-                    }
-                    if ui.button("Cancel").clicked() {
-                        commands.remove_resource::<ActiveBinding>();
+    Window::new(format!(
+        "Binding \"{}\"",
+        active_binding.action.metadata().name
+    ))
+    .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .show(egui.ctx_mut(), |ui| {
+        if let Some(conflict) = &active_binding.conflict {
+            ui.label(format!(
+                "Input \"{}\" is already used by \"{}\"",
+                conflict.input_button,
+                conflict.action.metadata().name
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("Replace").clicked() {
+                    control_settings
+                        .input
+                        .remove(conflict.action, conflict.input_button);
+                    control_settings.input.insert_at(
+                        conflict.input_button,
+                        active_binding.action,
+                        active_binding.index,
+                    );
+                    commands.remove_resource::<ActiveBinding>();
+                }
+                if ui.button("Cancel").clicked() {
+                    commands.remove_resource::<ActiveBinding>();
+                }
+            });
+        } else {
+            ui.label("Press any key now or Esc to cancel");
+            if ui_action_state.just_pressed(UiAction::Back) {
+                commands.remove_resource::<ActiveBinding>();
+            } else if let Some(input_button) = input_events.input_button() {
+                let conflict_action = control_settings.input.iter().find_map(|(inputs, action)| {
+                    if action != active_binding.action && inputs.contains(&input_button.into()) {
+                        return Some(action);
                     }
+                    None
                 });
-            } else {
-                ui.label("Press any key now or Esc to cancel");
-                if ui_action_state.just_pressed(UiAction::Back) {
+                if let Some(action) = conflict_action {
+                    active_binding.conflict.replace(BindingConflict {
+                        action,
+                        input_button,
+                    });
+                } else {
+                    control_settings.input.insert_at(
+                        input_button,
+                        active_binding.action,
+                        active_binding.index,
+                    );
                     commands.remove_resource::<ActiveBinding>();
-                } else if let Some(input_button) = input_events.input_button() {
-                    let conflict_action =
-                        control_settings.input.iter().find_map(|(inputs, action)| {
-                            if action != active_binding.action
-                                && inputs.contains(&input_button.into())
-                            {
-                                return Some(action);
-                            }
-                            None
-                        });
-                    if let Some(action) = conflict_action {
-                        active_binding.conflict.replace(BindingConflict {
-                            action,
-                            input_button,
-                        });
-                    } else {
-                        control_settings.input.insert_at(
-                            input_button,
-                            active_binding.action,
-                            active_binding.index,
-                        );
-                        commands.remove_resource::<ActiveBinding>();
-                    }
                 }
             }
-        });
+        }
+    });
 }
 
-#[derive(Actionlike, Debug, PartialEq, Clone, Copy, Display)]
+#[derive(Actionlike, Debug, PartialEq, Clone, Copy)]
 pub(crate) enum ControlAction {
     // Movement
     Forward,
     Backward,
     Left,
     Right,
+    #[actionlike(description = "Leap into the air")]
     Jump,
     // Abilities activation
+    #[actionlike(name = "Base attack")]
     BaseAttack,
+    #[actionlike(name = "Ability 1")]
     Ability1,
+    #[actionlike(name = "Ability 2")]
     Ability2,
+    #[actionlike(name = "Ability 3")]
     Ability3,
+    #[actionlike(description = "Your most powerful ability, which must be charged up")]
     Ultimate,
 }
 
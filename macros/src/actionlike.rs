@@ -1,8 +1,9 @@
+use crate::attributes::VariantAttributes;
 use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use proc_macro_crate::{crate_name, FoundCrate};
-use quote::quote;
-use syn::{Data, DeriveInput, Ident};
+use quote::{quote, quote_spanned};
+use syn::{Data, DeriveInput, Error, Ident, LitStr};
 
 /// This approach and implementation is inspired by the `strum` crate,
 /// Copyright (c) 2019 Peter Glotfelty
@@ -42,11 +43,28 @@ pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
     // Populate the array
     let mut get_at_match_items = Vec::new();
     let mut index_match_items = Vec::new();
+    let mut default_bindings = Vec::new();
+    let mut metadata_match_items = Vec::new();
+    let mut kind_match_items = Vec::new();
+    let mut any_kind_declared = false;
+    let mut nested_default_bindings = Vec::new();
+    let mut range_checks = Vec::new();
 
-    for (index, variant) in variants.iter().enumerate() {
+    // Variants with a declared range or nested actions take up several indices.
+    // The number of nested actions is only known by the compiler,
+    // so the index of each variant is the sum of a literal and the sizes of the preceding nested types.
+    let mut literal_index: usize = 0;
+    let mut nested_sizes = Vec::new();
+
+    for variant in variants.iter() {
         // The name of the enum variant
         let variant_identifier = variant.ident.clone();
 
+        let attributes = match VariantAttributes::parse(&variant.attrs) {
+            Ok(attributes) => attributes,
+            Err(error) => return error.to_compile_error(),
+        };
+
         let get_at_params = match &variant.fields {
             // Unit fields have no parameters
             syn::Fields::Unit => quote! {},
@@ -84,19 +102,232 @@ pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
             }
         };
 
+        let index = quote!((#literal_index #(+ #nested_sizes)*));
+
         // Match items
-        get_at_match_items.push(quote! {
-            #index => Some(#enum_name::#variant_identifier #get_at_params),
+        if let Some(nested_span) = attributes.nested {
+            let field_type = match &variant.fields {
+                syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+                _ => {
+                    return Error::new(
+                        nested_span,
+                        "`nested` can only be used on variants with a single unnamed field, such as `Movement(MovementAction)`",
+                    )
+                    .to_compile_error()
+                }
+            };
+
+            let has_other_settings = !attributes.keys.is_empty()
+                || !attributes.gamepad_buttons.is_empty()
+                || !attributes.mouse_buttons.is_empty()
+                || attributes.name.is_some()
+                || attributes.description.is_some()
+                || attributes.rebindable.is_some()
+                || attributes.kind.is_some()
+                || attributes.range.is_some();
+            if has_other_settings {
+                return Error::new(
+                    nested_span,
+                    "only `category` can be declared for `nested` variants: declare everything else on the nested type",
+                )
+                .to_compile_error();
+            }
+
+            let nested_trait = quote!(<#field_type as #crate_path::Actionlike>);
+            let size = quote!(#nested_trait::N_VARIANTS);
+
+            get_at_match_items.push(quote! {
+                index if (#index..#index + #size).contains(&index) => #nested_trait::get_at(index - #index).map(#enum_name::#variant_identifier),
+            });
+
+            index_match_items.push(quote! {
+                #enum_name::#variant_identifier(action) => #index + #nested_trait::index(action),
+            });
+
+            // Nested actions without a category of their own are grouped under the `category` setting, or the name of the variant
+            let category = attributes
+                .category
+                .map(|category| category.value())
+                .unwrap_or_else(|| variant_identifier.to_string());
+
+            metadata_match_items.push(quote! {
+                #enum_name::#variant_identifier(action) => {
+                    let metadata = #nested_trait::metadata(action);
+                    #crate_path::ActionMetadata {
+                        category: metadata.category.or(::core::option::Option::Some(#category)),
+                        ..metadata
+                    }
+                }
+            });
+
+            kind_match_items.push(quote! {
+                #enum_name::#variant_identifier(action) => #nested_trait::kind(action),
+            });
+            any_kind_declared = true;
+
+            nested_default_bindings.push(quote! {
+                bindings.extend(
+                    #nested_trait::default_bindings()
+                        .into_iter()
+                        .map(|(input, action)| (input, #enum_name::#variant_identifier(action))),
+                );
+            });
+
+            nested_sizes.push(size);
+            continue;
+        }
+
+        if let Some(range) = &attributes.range {
+            let field_type = match &variant.fields {
+                syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+                _ => {
+                    return Error::new(
+                        range.span(),
+                        "`range` can only be used on variants with a single unnamed field, such as `Hotbar(u8)`",
+                    )
+                    .to_compile_error();
+                }
+            };
+
+            let has_default_bindings = !attributes.keys.is_empty()
+                || !attributes.gamepad_buttons.is_empty()
+                || !attributes.mouse_buttons.is_empty();
+            if has_default_bindings {
+                return Error::new(
+                    range.span(),
+                    "default bindings cannot be declared for variants with a `range`",
+                )
+                .to_compile_error();
+            }
+
+            let (start, end, len) = (range.start, range.end, range.len());
+            let out_of_range_message = format!(
+                "the value stored in `{enum_name}::{variant_identifier}` is outside of its declared range"
+            );
+
+            // Values are converted to and from `i128`, so the whole range must fit in the field type
+            let too_wide_message = format!(
+                "the `range` of `{enum_name}::{variant_identifier}` does not fit in its field type"
+            );
+            let last = end - 1;
+            range_checks.push(quote_spanned! {range.span()=>
+                const _: () = ::core::assert!(
+                    #start >= <#field_type>::MIN as i128 && #last <= <#field_type>::MAX as i128,
+                    #too_wide_message
+                );
+            });
+
+            get_at_match_items.push(quote! {
+                index if (#index..#index + #len).contains(&index) => Some(#enum_name::#variant_identifier(((index - #index) as i128 + #start) as _)),
+            });
+
+            index_match_items.push(quote! {
+                #enum_name::#variant_identifier(value) => {
+                    let value = *value as i128;
+                    assert!((#start..#end).contains(&value), #out_of_range_message);
+                    #index + (value - #start) as usize
+                }
+            });
+
+            literal_index += len;
+        } else {
+            get_at_match_items.push(quote! {
+                index if index == #index => Some(#enum_name::#variant_identifier #get_at_params),
+            });
+
+            index_match_items.push(quote! {
+                #enum_name::#variant_identifier #index_params => #index,
+            });
+
+            literal_index += 1;
+        }
+
+        // Default bindings
+        let keys = attributes
+            .keys
+            .iter()
+            .map(|key| quote!(#crate_path::__macro_exports::KeyCode::#key));
+        let gamepad_buttons = attributes
+            .gamepad_buttons
+            .iter()
+            .map(|button| quote!(#crate_path::__macro_exports::GamepadButtonType::#button));
+        let mouse_buttons = attributes
+            .mouse_buttons
+            .iter()
+            .map(|button| quote!(#crate_path::__macro_exports::MouseButton::#button));
+
+        // Metadata, named after the variant unless otherwise specified
+        let name = attributes
+            .name
+            .map(|name| name.value())
+            .unwrap_or_else(|| variant_identifier.to_string());
+        let category = option_tokens(attributes.category);
+        let description = option_tokens(attributes.description);
+        let rebindable = attributes
+            .rebindable
+            .is_none_or(|rebindable| rebindable.value);
+
+        metadata_match_items.push(quote! {
+            #enum_name::#variant_identifier #index_params => #crate_path::ActionMetadata {
+                name: #name,
+                category: #category,
+                description: #description,
+                rebindable: #rebindable,
+            },
         });
 
-        index_match_items.push(quote! {
-            #enum_name::#variant_identifier #index_params => #index,
+        // Kind
+        let kind = match &attributes.kind {
+            Some(kind) => {
+                any_kind_declared = true;
+                quote!(::core::option::Option::Some(#crate_path::ActionKind::#kind))
+            }
+            None => quote!(::core::option::Option::None),
+        };
+
+        kind_match_items.push(quote! {
+            #enum_name::#variant_identifier #index_params => #kind,
         });
+
+        for input in keys.chain(gamepad_buttons).chain(mouse_buttons) {
+            default_bindings.push(quote! {
+                (#crate_path::user_input::UserInput::from(#input), #enum_name::#variant_identifier #get_at_params),
+            });
+        }
     }
 
-    let n_variants = variants.iter().len();
+    let n_variants = quote!(#literal_index #(+ #nested_sizes)*);
+
+    // Only override the default implementations if kinds or bindings were declared
+    let kind_fn = if any_kind_declared {
+        quote! {
+            fn kind(&self) -> ::core::option::Option<#crate_path::ActionKind> {
+                match self {
+                    #(#kind_match_items)*
+                    _ => unreachable!()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let default_bindings_fn = if default_bindings.is_empty() && nested_default_bindings.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn default_bindings() -> ::std::vec::Vec<(#crate_path::user_input::UserInput, Self)> {
+                #[allow(unused_mut)]
+                let mut bindings = ::std::vec![#(#default_bindings)*];
+                #(#nested_default_bindings)*
+                bindings
+            }
+        }
+    };
 
     quote! {
+        #(#range_checks)*
+
         impl #impl_generics #crate_path::Actionlike for #enum_name #type_generics #where_clause {
             const N_VARIANTS: usize = #n_variants;
 
@@ -113,6 +344,25 @@ pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
                     _ => unreachable!()
                 }
             }
+
+            fn metadata(&self) -> #crate_path::ActionMetadata {
+                match self {
+                    #(#metadata_match_items)*
+                    _ => unreachable!()
+                }
+            }
+
+            #kind_fn
+
+            #default_bindings_fn
         }
     }
 }
+
+/// Converts an optional string literal into the tokens of an `Option<&'static str>`
+fn option_tokens(lit_str: Option<LitStr>) -> TokenStream {
+    match lit_str {
+        Some(lit_str) => quote!(::core::option::Option::Some(#lit_str)),
+        None => quote!(::core::option::Option::None),
+    }
+}
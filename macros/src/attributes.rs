@@ -0,0 +1,204 @@
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Ident, Lit, LitBool, LitInt, LitStr, Result, Token};
+
+/// The settings stored in the `#[actionlike(...)]` attributes of a single enum variant
+#[derive(Default)]
+pub(crate) struct VariantAttributes {
+    /// The `KeyCode` variants bound to this action by default
+    pub(crate) keys: Vec<Ident>,
+    /// The `GamepadButtonType` variants bound to this action by default
+    pub(crate) gamepad_buttons: Vec<Ident>,
+    /// The `MouseButton` variants bound to this action by default
+    pub(crate) mouse_buttons: Vec<Ident>,
+    /// The human-readable name of the action
+    pub(crate) name: Option<LitStr>,
+    /// The group of actions this action belongs to
+    pub(crate) category: Option<LitStr>,
+    /// A longer explanation of the action, such as a tooltip
+    pub(crate) description: Option<LitStr>,
+    /// Can players change the bindings of this action?
+    pub(crate) rebindable: Option<LitBool>,
+    /// The `ActionKind` variant describing the data this action carries
+    pub(crate) kind: Option<Ident>,
+    /// The values of the variant's field that are each treated as a distinct action
+    pub(crate) range: Option<ActionRange>,
+    /// Is the variant's field another `Actionlike` type, whose actions are each treated as a distinct action?
+    pub(crate) nested: Option<Span>,
+}
+
+impl VariantAttributes {
+    /// Collects the settings from every `#[actionlike(...)]` attribute in `attrs`
+    ///
+    /// Bindings may be repeated, but every other setting may only be set once.
+    /// All other attributes are ignored.
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut attributes = VariantAttributes::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("actionlike")) {
+            let settings =
+                attr.parse_args_with(Punctuated::<Setting, Token![,]>::parse_terminated)?;
+
+            for setting in settings {
+                let span = setting.name.span();
+                match setting.name.to_string().as_str() {
+                    "key" => attributes.keys.push(variant_ident(&setting)?),
+                    "gamepad" => attributes.gamepad_buttons.push(variant_ident(&setting)?),
+                    "mouse" => attributes.mouse_buttons.push(variant_ident(&setting)?),
+                    "name" => set_once(&mut attributes.name, str_lit(&setting)?, span)?,
+                    "category" => set_once(&mut attributes.category, str_lit(&setting)?, span)?,
+                    "description" => set_once(&mut attributes.description, str_lit(&setting)?, span)?,
+                    "rebindable" => set_once(&mut attributes.rebindable, bool_lit(&setting)?, span)?,
+                    "kind" => set_once(&mut attributes.kind, variant_ident(&setting)?, span)?,
+                    "range" => match setting.value {
+                        SettingValue::Range(range) => set_once(&mut attributes.range, range, span)?,
+                        SettingValue::Flag => {
+                            return Err(Error::new(span, "expected a range, such as `0..10`"))
+                        }
+                        SettingValue::Lit(_) => unreachable!(),
+                    },
+                    "nested" => match setting.value {
+                        SettingValue::Flag => set_once(&mut attributes.nested, span, span)?,
+                        SettingValue::Lit(_) | SettingValue::Range(_) => {
+                            return Err(Error::new(span, "`nested` does not take a value"))
+                        }
+                    },
+                    _ => {
+                        return Err(Error::new(
+                            span,
+                            "unknown setting: expected one of `key`, `gamepad`, `mouse`, `name`, `category`, `description`, `rebindable`, `kind`, `range` or `nested`",
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// A single `setting = value` pair, or a `setting` flag without a value
+struct Setting {
+    name: Ident,
+    value: SettingValue,
+}
+
+enum SettingValue {
+    Lit(Lit),
+    Range(ActionRange),
+    Flag,
+}
+
+impl Parse for Setting {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        if !input.peek(Token![=]) {
+            return Ok(Setting {
+                name,
+                value: SettingValue::Flag,
+            });
+        }
+        input.parse::<Token![=]>()?;
+
+        // Ranges are not literals, so they must be parsed by hand
+        let value = if name == "range" {
+            SettingValue::Range(input.parse()?)
+        } else {
+            SettingValue::Lit(input.parse()?)
+        };
+
+        Ok(Setting { name, value })
+    }
+}
+
+/// A non-empty range of integers, such as `0..10` or `1..=9`
+pub(crate) struct ActionRange {
+    /// The first value in the range
+    pub(crate) start: i128,
+    /// The value after the last value in the range
+    pub(crate) end: i128,
+    span: Span,
+}
+
+impl ActionRange {
+    /// The number of values in the range
+    pub(crate) fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    pub(crate) fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl Parse for ActionRange {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
+        let start = parse_integer(input)?;
+        let end = if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            parse_integer(input)? + 1
+        } else {
+            input.parse::<Token![..]>()?;
+            parse_integer(input)?
+        };
+
+        if end <= start {
+            return Err(Error::new(span, "the range must not be empty"));
+        }
+
+        Ok(ActionRange { start, end, span })
+    }
+}
+
+/// Parses an integer literal, which may be negative
+fn parse_integer(input: ParseStream) -> Result<i128> {
+    let negative = input.peek(Token![-]);
+    if negative {
+        input.parse::<Token![-]>()?;
+    }
+
+    let value = input.parse::<LitInt>()?.base10_parse::<i128>()?;
+    Ok(if negative { -value } else { value })
+}
+
+/// Stores the `value` of a setting, failing at `span` if it was already set
+fn set_once<T>(setting: &mut Option<T>, value: T, span: Span) -> Result<()> {
+    if setting.is_some() {
+        return Err(Error::new(span, "this setting was already set"));
+    }
+    *setting = Some(value);
+    Ok(())
+}
+
+fn str_lit(setting: &Setting) -> Result<LitStr> {
+    match &setting.value {
+        SettingValue::Lit(Lit::Str(lit_str)) => Ok(lit_str.clone()),
+        _ => Err(Error::new(setting.name.span(), "expected a string literal")),
+    }
+}
+
+fn bool_lit(setting: &Setting) -> Result<LitBool> {
+    match &setting.value {
+        SettingValue::Lit(Lit::Bool(lit_bool)) => Ok(lit_bool.clone()),
+        _ => Err(Error::new(
+            setting.name.span(),
+            "expected `true` or `false`",
+        )),
+    }
+}
+
+/// Converts a string literal naming an enum variant (such as `"Space"`) into an identifier
+///
+/// The identifier keeps the span of the literal, so using a variant that does not exist
+/// produces a compile error pointing at the attribute.
+fn variant_ident(setting: &Setting) -> Result<Ident> {
+    let lit_str = str_lit(setting)?;
+    lit_str.parse::<Ident>().map_err(|_| {
+        Error::new(
+            lit_str.span(),
+            format!("`{}` is not a valid variant name", lit_str.value()),
+        )
+    })
+}
@@ -0,0 +1,305 @@
+//! Callbacks that run when the actions of a specific entity are triggered
+//!
+//! Reading the [`ActionState`] in your own systems is the usual way to respond to actions,
+//! but scripting layers and one-off interactions often want to attach behavior to a single entity instead:
+//! "when `Interact` is just pressed on this door, open it".
+//! Add an [`ActionHandlers`] component next to the entity's [`ActionState`] to do so.
+//!
+//! Handlers are run by [`run_action_handlers`](crate::systems::run_action_handlers),
+//! which is added by [`InputManagerPlugin::with_action_handlers`](crate::plugin::InputManagerPlugin::with_action_handlers).
+//! It runs after the [`ActionState`] is updated from inputs and from UI interactions,
+//! so handlers see the same state as the rest of the frame.
+//! The handlers of each entity run in the order they were registered.
+//!
+//! Each handler returns a [`HandlerResponse`].
+//! Returning [`HandlerResponse::Consume`] calls [`ActionState::consume`] on the action,
+//! so later handlers and systems will not see it as pressed until it is pressed again.
+//!
+//! # Example
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::action_handlers::{ActionHandlers, HandlerResponse, HandlerTrigger};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+//! enum Action {
+//!     Interact,
+//! }
+//!
+//! #[derive(Component)]
+//! struct Door {
+//!     open: bool,
+//! }
+//!
+//! fn open_door(In(entity): In<Entity>, mut doors: Query<&mut Door>) -> HandlerResponse {
+//!     doors.get_mut(entity).unwrap().open = true;
+//!     HandlerResponse::Consume
+//! }
+//!
+//! let mut handlers = ActionHandlers::<Action>::default();
+//! handlers.insert_system(HandlerTrigger::JustPressed, Action::Interact, open_door);
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!     .add_plugin(InputManagerPlugin::<Action>::default().with_action_handlers());
+//!
+//! app.world.spawn((
+//!     Door { open: false },
+//!     InputManagerBundle::<Action>::default(),
+//!     handlers,
+//! ));
+//! ```
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::*;
+use bevy::ecs::system::BoxedSystem;
+
+/// Actions of the entity that this component is attached to, paired with the handlers to run when they are triggered
+///
+/// See the [module-level documentation](crate::action_handlers) for more information.
+#[derive(Component)]
+pub struct ActionHandlers<A: Actionlike> {
+    handlers: Vec<ActionHandler<A>>,
+}
+
+/// A single handler registered in [`ActionHandlers`]
+struct ActionHandler<A: Actionlike> {
+    trigger: HandlerTrigger,
+    action: A,
+    callback: Box<dyn FnMut(Entity, &mut World) -> HandlerResponse + Send + Sync>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Actionlike> Default for ActionHandlers<A> {
+    fn default() -> Self {
+        Self {
+            handlers: Vec::new(),
+        }
+    }
+}
+
+impl<A: Actionlike> ActionHandlers<A> {
+    /// Registers a `handler` to run whenever the `trigger` is met for `action`
+    ///
+    /// The handler is passed the entity that this component belongs to,
+    /// and has exclusive access to the [`World`].
+    pub fn insert(
+        &mut self,
+        trigger: HandlerTrigger,
+        action: A,
+        handler: impl FnMut(Entity, &mut World) -> HandlerResponse + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.handlers.push(ActionHandler {
+            trigger,
+            action,
+            callback: Box::new(handler),
+        });
+        self
+    }
+
+    /// Registers a `system` to run whenever the `trigger` is met for `action`
+    ///
+    /// The system is passed the entity that this component belongs to using [`In<Entity>`].
+    /// Its commands are applied as soon as it has run, before the next handler runs.
+    pub fn insert_system<M>(
+        &mut self,
+        trigger: HandlerTrigger,
+        action: A,
+        system: impl IntoSystem<Entity, HandlerResponse, M>,
+    ) -> &mut Self {
+        let mut system: BoxedSystem<Entity, HandlerResponse> =
+            Box::new(IntoSystem::into_system(system));
+        let mut initialized = false;
+
+        self.insert(trigger, action, move |entity, world| {
+            // Systems can only be initialized once the world is available
+            if !initialized {
+                system.initialize(world);
+                initialized = true;
+            }
+
+            let response = system.run(entity, world);
+            system.apply_buffers(world);
+            response
+        })
+    }
+
+    /// Removes all handlers registered for `action`
+    pub fn clear_action(&mut self, action: A) {
+        self.handlers
+            .retain(|handler| handler.action.index() != action.index());
+    }
+
+    /// The number of registered handlers
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Are there no registered handlers?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Runs each handler whose trigger is met by the [`ActionState`] of `entity`, in the order they were registered
+    ///
+    /// Handlers registered while running are kept, but will not run until the next call.
+    pub fn run(entity: Entity, world: &mut World) {
+        // Handlers are removed from the component while running, so that they can freely access the world
+        let mut handlers = match world.get_mut::<ActionHandlers<A>>(entity) {
+            Some(mut action_handlers) => std::mem::take(&mut action_handlers.handlers),
+            None => return,
+        };
+
+        for handler in handlers.iter_mut() {
+            // Handlers may have removed the action state, or despawned the entity
+            let triggered = match world.get::<ActionState<A>>(entity) {
+                Some(action_state) => handler
+                    .trigger
+                    .is_triggered(action_state, handler.action.clone()),
+                None => break,
+            };
+
+            if triggered && (handler.callback)(entity, world) == HandlerResponse::Consume {
+                if let Some(mut action_state) = world.get_mut::<ActionState<A>>(entity) {
+                    // Released actions have nothing to consume
+                    if action_state.pressed(handler.action.clone()) {
+                        action_state.consume(handler.action.clone());
+                    }
+                }
+            }
+        }
+
+        if let Some(mut action_handlers) = world.get_mut::<ActionHandlers<A>>(entity) {
+            handlers.append(&mut action_handlers.handlers);
+            action_handlers.handlers = handlers;
+        }
+    }
+}
+
+/// When should a handler in [`ActionHandlers`] run?
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandlerTrigger {
+    /// When [`ActionState::just_pressed`] is true for the action
+    JustPressed,
+    /// Every time handlers run while [`ActionState::pressed`] is true for the action
+    Pressed,
+    /// When [`ActionState::just_released`] is true for the action
+    JustReleased,
+}
+
+impl HandlerTrigger {
+    /// Is this trigger met for `action` in the provided `action_state`?
+    #[must_use]
+    pub fn is_triggered<A: Actionlike>(&self, action_state: &ActionState<A>, action: A) -> bool {
+        match self {
+            HandlerTrigger::JustPressed => action_state.just_pressed(action),
+            HandlerTrigger::Pressed => action_state.pressed(action),
+            HandlerTrigger::JustReleased => action_state.just_released(action),
+        }
+    }
+}
+
+/// What should happen to an action after a handler in [`ActionHandlers`] has run?
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandlerResponse {
+    /// Leave the action as it is, so later handlers and systems can also respond to it
+    Propagate,
+    /// Consume the action using [`ActionState::consume`], so that nothing else responds to this press
+    Consume,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    enum Action {
+        Interact,
+        Jump,
+    }
+
+    #[derive(Resource, Default)]
+    struct Calls(Vec<&'static str>);
+
+    fn log(
+        name: &'static str,
+        response: HandlerResponse,
+    ) -> impl FnMut(Entity, &mut World) -> HandlerResponse {
+        move |_, world| {
+            world.resource_mut::<Calls>().0.push(name);
+            response
+        }
+    }
+
+    #[test]
+    fn handlers_run_in_order_until_consumed() {
+        let mut world = World::new();
+        world.init_resource::<Calls>();
+
+        let mut handlers = ActionHandlers::<Action>::default();
+        handlers
+            .insert(
+                HandlerTrigger::JustPressed,
+                Action::Interact,
+                log("first", HandlerResponse::Propagate),
+            )
+            .insert(
+                HandlerTrigger::Pressed,
+                Action::Interact,
+                log("second", HandlerResponse::Consume),
+            )
+            .insert(
+                HandlerTrigger::JustPressed,
+                Action::Interact,
+                log("third", HandlerResponse::Propagate),
+            )
+            .insert(
+                HandlerTrigger::JustPressed,
+                Action::Jump,
+                log("jump", HandlerResponse::Propagate),
+            );
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Interact);
+        let entity = world.spawn((action_state, handlers)).id();
+
+        ActionHandlers::<Action>::run(entity, &mut world);
+
+        assert_eq!(world.resource::<Calls>().0, ["first", "second"]);
+        let action_state = world.get::<ActionState<Action>>(entity).unwrap();
+        assert!(action_state.released(Action::Interact));
+        assert!(action_state.action_data(Action::Interact).consumed);
+        assert_eq!(
+            world.get::<ActionHandlers<Action>>(entity).unwrap().len(),
+            4
+        );
+    }
+
+    #[test]
+    fn handlers_can_despawn_their_entity() {
+        let mut world = World::new();
+
+        let mut handlers = ActionHandlers::<Action>::default();
+        handlers.insert(
+            HandlerTrigger::JustPressed,
+            Action::Jump,
+            |entity, world| {
+                world.despawn(entity);
+                HandlerResponse::Consume
+            },
+        );
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Jump);
+        let entity = world.spawn((action_state, handlers)).id();
+
+        ActionHandlers::<Action>::run(entity, &mut world);
+        assert!(world.get_entity(entity).is_none());
+    }
+}
@@ -1,7 +1,8 @@
 //! This module contains [`ActionState`] and its supporting methods and impls.
 
-use crate::Actionlike;
+use crate::user_input::TriggerMode;
 use crate::{axislike::DualAxisData, buttonlike::ButtonState};
+use crate::{ActionKind, Actionlike};
 
 use bevy::ecs::{component::Component, entity::Entity};
 use bevy::prelude::Resource;
@@ -22,7 +23,8 @@ pub struct ActionData {
     /// See [`ActionState::action_value()`] for more details.
     ///
     /// **Warning:** this value may not be bounded as you might expect.
-    /// Consider clamping this to account for multiple triggering inputs.
+    /// Consider clamping this to account for multiple triggering inputs,
+    /// or set the [`AggregationPolicy`](crate::user_input::AggregationPolicy) of the action.
     pub value: f32,
     /// The [`AxisPair`] of the binding that triggered the action.
     ///
@@ -89,28 +91,201 @@ pub struct ActionState<A: Actionlike> {
     ///
     /// The position in this vector corresponds to [`Actionlike::index`].
     action_data: Vec<ActionData>,
+    /// The [`ActionSettings`] of each action
+    ///
+    /// These configure how actions respond to their inputs, rather than being part of their state,
+    /// so they are not serialized, and are kept by [`ActionState::set_action_data`].
+    #[serde(skip, default = "default_settings::<A>")]
+    settings: Vec<ActionSettings>,
+    /// The [`ActionTracking`] of each action
+    ///
+    /// Like the settings, this is not serialized, and is kept by [`ActionState::set_action_data`].
+    #[serde(skip, default = "default_tracking::<A>")]
+    tracking: Vec<ActionTracking>,
     #[reflect(ignore)]
     _phantom: PhantomData<A>,
 }
 
+/// The [`ActionSettings`] of a new [`ActionState`], which are the defaults for every action
+fn default_settings<A: Actionlike>() -> Vec<ActionSettings> {
+    vec![ActionSettings::default(); A::N_VARIANTS]
+}
+
+/// The [`ActionTracking`] of a new [`ActionState`], which has not seen any inputs yet
+fn default_tracking<A: Actionlike>() -> Vec<ActionTracking> {
+    vec![ActionTracking::default(); A::N_VARIANTS]
+}
+
 impl<A: Actionlike> ActionState<A> {
     /// Updates the [`ActionState`] based on a vector of [`ActionData`], ordered by [`Actionlike::id`](Actionlike).
     ///
     /// The `action_data` is typically constructed from [`InputMap::which_pressed`](crate::input_map::InputMap),
     /// which reads from the assorted [`Input`](bevy::input::Input) resources.
     pub fn update(&mut self, action_data: Vec<ActionData>) {
+        self.update_with_events(action_data, None, |_| ());
+    }
+
+    /// Updates the [`ActionState`] in the same way as [`ActionState::update`], describing each change with an [`ActionEvent`]
+    ///
+    /// For each action, `send_event` is called with [`ActionEvent::Pressed`], [`ActionEvent::Released`] or [`ActionEvent::Held`]
+    /// if it is pressed after the update, or was before it.
+    /// [`ActionEvent::ValueChanged`] is also sent if the value of the action changed.
+    /// Each event stores the `entity` that this [`ActionState`] belongs to, if any.
+    ///
+    /// # Example
+    /// ```rust
+    /// use leafwing_input_manager::action_state::{ActionData, ActionEvent};
+    /// use leafwing_input_manager::buttonlike::ButtonState;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Action {
+    ///     Jump,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let mut action_data = vec![ActionData::default(); Action::N_VARIANTS];
+    /// action_data[Action::Jump.index()].state = ButtonState::JustPressed;
+    /// action_data[Action::Jump.index()].value = 1.0;
+    ///
+    /// let mut events = Vec::new();
+    /// action_state.update_with_events(action_data, None, |event| events.push(event));
+    ///
+    /// assert_eq!(
+    ///     events,
+    ///     vec![
+    ///         ActionEvent::Pressed { entity: None, action: Action::Jump },
+    ///         ActionEvent::ValueChanged { entity: None, action: Action::Jump, value: 1.0 },
+    ///     ]
+    /// );
+    /// ```
+    pub fn update_with_events(
+        &mut self,
+        action_data: Vec<ActionData>,
+        entity: Option<Entity>,
+        send_event: impl FnMut(ActionEvent<A>),
+    ) {
+        self.update_inner(action_data, entity, false, send_event);
+    }
+
+    /// Updates the [`ActionState`] in the same way as [`ActionState::update_with_events`],
+    /// but does not release actions that were pressed since the last [`ActionState::tick`]
+    ///
+    /// Use this when several updates can occur between ticks, such as when ticking once per fixed timestep,
+    /// so that every press is seen as [`just_pressed`](ActionState::just_pressed) by at least one tick.
+    /// Actions whose inputs were released in the meantime are released by the first update after the tick.
+    pub fn update_buffered(
+        &mut self,
+        action_data: Vec<ActionData>,
+        entity: Option<Entity>,
+        send_event: impl FnMut(ActionEvent<A>),
+    ) {
+        self.update_inner(action_data, entity, true, send_event);
+    }
+
+    /// Shared implementation of [`ActionState::update_with_events`] and [`ActionState::update_buffered`]
+    ///
+    /// If `buffered` is `true`, actions that were just pressed are not released.
+    fn update_inner(
+        &mut self,
+        action_data: Vec<ActionData>,
+        entity: Option<Entity>,
+        buffered: bool,
+        mut send_event: impl FnMut(ActionEvent<A>),
+    ) {
         assert_eq!(action_data.len(), A::N_VARIANTS);
 
         for (i, action) in A::variants().enumerate() {
-            match action_data[i].state {
-                ButtonState::JustPressed => self.press(action),
-                ButtonState::Pressed => self.press(action),
-                ButtonState::JustReleased => self.release(action),
-                ButtonState::Released => self.release(action),
+            let was_pressed = self.pressed(action.clone());
+            let previous_value = self.action_data[i].value;
+
+            let triggered = std::mem::take(&mut self.tracking[i].triggered);
+            let inputs_pressed = action_data[i].state.pressed() || triggered;
+            if self.settings[i].toggle {
+                // Toggled actions only change when their inputs are pressed again
+                if inputs_pressed && !self.tracking[i].inputs_pressed {
+                    if self.released(action.clone()) {
+                        self.press(action.clone());
+                    } else if !(buffered && self.just_pressed(action.clone())) {
+                        self.release(action.clone());
+                    }
+                } else if !inputs_pressed {
+                    // Consumed actions can be toggled on again once their inputs are released
+                    self.action_data[i].consumed = false;
+                }
+            } else if inputs_pressed {
+                self.press(action.clone());
+            } else if !(buffered && self.just_pressed(action.clone())) {
+                self.release(action.clone());
+            }
+            self.tracking[i].inputs_pressed = inputs_pressed;
+
+            match (was_pressed, self.pressed(action.clone())) {
+                (false, true) => send_event(ActionEvent::Pressed {
+                    entity,
+                    action: action.clone(),
+                }),
+                (true, false) => send_event(ActionEvent::Released {
+                    entity,
+                    action: action.clone(),
+                    duration: self.previous_duration(action.clone()),
+                }),
+                (true, true) => send_event(ActionEvent::Held {
+                    entity,
+                    action: action.clone(),
+                    duration: self.current_duration(action.clone()),
+                }),
+                (false, false) => (),
             }
 
             self.action_data[i].axis_pair = action_data[i].axis_pair;
             self.action_data[i].value = action_data[i].value;
+
+            if self.action_data[i].value != previous_value {
+                send_event(ActionEvent::ValueChanged {
+                    entity,
+                    action,
+                    value: self.action_data[i].value,
+                });
+            }
+        }
+    }
+
+    /// Tracks the held bindings of each action that press it once they are released
+    ///
+    /// `held_triggers` stores the [`TriggerMode`] of those bindings for each action, ordered by [`Actionlike::index`],
+    /// and is typically constructed from [`InputMap::held_triggers`](crate::input_map::InputMap::held_triggers).
+    /// Call this before each [`ActionState::update`]: actions whose bindings were just released are pressed by that update.
+    pub fn update_held_triggers(&mut self, held_triggers: Vec<Option<TriggerMode>>) {
+        assert_eq!(held_triggers.len(), A::N_VARIANTS);
+
+        for (tracking, held_trigger) in self.tracking.iter_mut().zip(held_triggers) {
+            let previous_trigger = std::mem::replace(&mut tracking.held_trigger, held_trigger);
+
+            tracking.triggered = match (previous_trigger, held_trigger) {
+                (None, Some(_)) => {
+                    tracking.held_timing.flip();
+                    tracking.cancelled = false;
+                    false
+                }
+                (Some(trigger), None) => {
+                    tracking.held_timing.flip();
+
+                    // Consuming the action while the bindings were held cancels it
+                    if std::mem::take(&mut tracking.cancelled) {
+                        false
+                    } else {
+                        match trigger {
+                            TriggerMode::Press => false,
+                            TriggerMode::Release => true,
+                            TriggerMode::Click { max_duration } => {
+                                tracking.held_timing.previous_duration <= max_duration
+                            }
+                        }
+                    }
+                }
+                _ => false,
+            };
         }
     }
 
@@ -168,6 +343,26 @@ impl<A: Actionlike> ActionState<A> {
                 ad.timing.tick(current_instant, previous_instant);
             }
         });
+
+        self.tracking
+            .iter_mut()
+            .filter(|tracking| tracking.held_trigger.is_some())
+            .for_each(|tracking| tracking.held_timing.tick(current_instant, previous_instant));
+
+        // Repeat held actions that are due
+        let settings = self.settings.iter().zip(self.tracking.iter_mut());
+        self.action_data
+            .iter_mut()
+            .zip(settings)
+            .for_each(|(ad, (settings, tracking))| {
+                if let (Some(repeat), ButtonState::Pressed) = (settings.repeat, ad.state) {
+                    let due = repeat.repeats_after(ad.timing.current_duration);
+                    if due > tracking.repeats {
+                        tracking.repeats = due;
+                        ad.state = ButtonState::JustPressed;
+                    }
+                }
+            });
     }
 
     /// A reference to the [`ActionData`] of the corresponding `action`
@@ -237,8 +432,9 @@ impl<A: Actionlike> ActionState<A> {
     /// `0.0..=1.0`.
     /// - Chord inputs will return the value of its first input.
     ///
-    /// If multiple inputs trigger the same game action at the same time, the value of each
-    /// triggering input will be added together.
+    /// If multiple inputs trigger the same game action at the same time, their values are combined
+    /// according to the [`AggregationPolicy`](crate::user_input::AggregationPolicy) of the action,
+    /// which adds them together by default.
     ///
     /// # Warning
     ///
@@ -262,8 +458,9 @@ impl<A: Actionlike> ActionState<A> {
     ///
     /// Chord inputs will return the [`DualAxisData`] of it's first input.
     ///
-    /// If multiple inputs with an axis pair trigger the same game action at the same time, the
-    /// value of each axis pair will be added together.
+    /// If multiple inputs with an axis pair are bound to the same game action, their axis pairs are combined
+    /// according to the [`AggregationPolicy`](crate::user_input::AggregationPolicy) of the action,
+    /// which adds them together by default.
     ///
     /// # Warning
     ///
@@ -280,6 +477,35 @@ impl<A: Actionlike> ActionState<A> {
             .map(|pair| DualAxisData::new(pair.x().clamp(-1.0, 1.0), pair.y().clamp(-1.0, 1.0)))
     }
 
+    /// Get the value of an [`ActionKind::Axis`] `action`, clamped to `[-1.0, 1.0]`
+    ///
+    /// Unlike [`clamped_value`](Self::clamped_value), this checks the declared [`Actionlike::kind`] of the `action`.
+    /// Actions whose kind is not declared are always accepted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `action` is declared to be of any kind other than [`ActionKind::Axis`].
+    #[track_caller]
+    pub fn axis_value(&self, action: A) -> f32 {
+        assert_kind(&action, ActionKind::Axis);
+        self.clamped_value(action)
+    }
+
+    /// Get the [`DualAxisData`] of an [`ActionKind::DualAxis`] `action`, clamped to `[-1.0, 1.0]`
+    ///
+    /// Unlike [`clamped_axis_pair`](Self::clamped_axis_pair), this checks the declared [`Actionlike::kind`] of the `action`,
+    /// and returns a zeroed [`DualAxisData`] if none of its inputs are active.
+    /// Actions whose kind is not declared are always accepted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `action` is declared to be of any kind other than [`ActionKind::DualAxis`].
+    #[track_caller]
+    pub fn dual_axis_pair(&self, action: A) -> DualAxisData {
+        assert_kind(&action, ActionKind::DualAxis);
+        self.clamped_axis_pair(action).unwrap_or_default()
+    }
+
     /// Manually sets the [`ActionData`] of the corresponding `action`
     ///
     /// You should almost always use more direct methods, as they are simpler and less error-prone.
@@ -318,6 +544,23 @@ impl<A: Actionlike> ActionState<A> {
         self.action_data[action.index()] = data;
     }
 
+    /// The [`ActionSettings`] of the corresponding `action`
+    ///
+    /// These are set using [`ActionState::set_toggle`], [`ActionState::set_repeat`] and [`ActionState::set_charge`].
+    #[inline]
+    #[must_use]
+    pub fn action_settings(&self, action: A) -> ActionSettings {
+        self.settings[action.index()]
+    }
+
+    /// Replaces the [`ActionSettings`] of the corresponding `action`
+    ///
+    /// As settings are not serialized, use this to configure copies of an [`ActionState`] received over the network.
+    #[inline]
+    pub fn set_action_settings(&mut self, action: A, settings: ActionSettings) {
+        self.settings[action.index()] = settings;
+    }
+
     /// Press the `action`
     ///
     /// No initial instant or reasons why the button was pressed will be recorded
@@ -332,6 +575,7 @@ impl<A: Actionlike> ActionState<A> {
 
         if self.released(action) {
             self.action_data[index].timing.flip();
+            self.tracking[index].repeats = 0;
         }
 
         self.action_data[index].state.press();
@@ -397,10 +641,206 @@ impl<A: Actionlike> ActionState<A> {
         let index = action.index();
         // This is the only difference from action_state.release(action)
         self.action_data[index].consumed = true;
+        // Bindings that press the action once released are cancelled until they are released
+        if self.tracking[index].held_trigger.is_some() {
+            self.tracking[index].cancelled = true;
+        }
         self.action_data[index].state.release();
         self.action_data[index].timing.flip();
     }
 
+    /// Sets whether the `action` is toggled by its inputs, rather than held
+    ///
+    /// Toggled actions are pressed by one press of their inputs, and stay pressed until their inputs are pressed again,
+    /// as is often offered for crouching, sprinting or aiming.
+    /// [`ActionState::update`] only changes their state when their inputs are newly pressed,
+    /// while their `value` and `axis_pair` still follow the inputs.
+    ///
+    /// As this is stored in each [`ActionState`], every player can choose between holding and toggling each action.
+    /// Actions are held by default.
+    /// Like the other [`ActionSettings`], this is not serialized with the [`ActionState`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use leafwing_input_manager::action_state::ActionData;
+    /// use leafwing_input_manager::buttonlike::ButtonState;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Action {
+    ///     Crouch,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// action_state.set_toggle(Action::Crouch, true);
+    ///
+    /// let mut inputs_pressed = vec![ActionData::default(); Action::N_VARIANTS];
+    /// inputs_pressed[Action::Crouch.index()].state = ButtonState::JustPressed;
+    /// let inputs_released = vec![ActionData::default(); Action::N_VARIANTS];
+    ///
+    /// // Pressing the inputs presses the action...
+    /// action_state.update(inputs_pressed.clone());
+    /// assert!(action_state.pressed(Action::Crouch));
+    ///
+    /// // ...which stays pressed once they are released...
+    /// action_state.update(inputs_released.clone());
+    /// assert!(action_state.pressed(Action::Crouch));
+    ///
+    /// // ...until they are pressed again
+    /// action_state.tick(bevy::utils::Instant::now(), bevy::utils::Instant::now());
+    /// action_state.update(inputs_pressed);
+    /// assert!(action_state.released(Action::Crouch));
+    /// ```
+    pub fn set_toggle(&mut self, action: A, toggle: bool) {
+        self.settings[action.index()].toggle = toggle;
+    }
+
+    /// Is the `action` toggled by its inputs, rather than held?
+    ///
+    /// See [`ActionState::set_toggle`] for more details.
+    #[must_use]
+    pub fn is_toggle(&self, action: A) -> bool {
+        self.settings[action.index()].toggle
+    }
+
+    /// Sets how the `action` is repeatedly pressed while it is held, or stops repeating it if `repeat` is [`None`]
+    ///
+    /// Once the action has been held for [`RepeatSettings::delay`], and then after every [`RepeatSettings::interval`],
+    /// [`ActionState::tick`] makes it [`just_pressed`](ActionState::just_pressed) again,
+    /// as is expected when navigating menus or typing.
+    /// The [`current_duration`](ActionState::current_duration) of the action is unchanged by repeats,
+    /// so this works in the same way for every kind of input that can press the action,
+    /// including gamepad sticks bound using a [`DualAxis`](crate::axislike::DualAxis).
+    ///
+    /// If several repeats are due in a single tick, the action is only just pressed once.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::action_state::RepeatSettings;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Action {
+    ///     Down,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// action_state.set_repeat(
+    ///     Action::Down,
+    ///     Some(RepeatSettings::new(Duration::from_millis(300), Duration::from_millis(80))),
+    /// );
+    ///
+    /// let start = Instant::now();
+    /// action_state.press(Action::Down);
+    /// action_state.tick(start, start);
+    /// assert!(!action_state.just_pressed(Action::Down));
+    ///
+    /// action_state.tick(start + Duration::from_millis(300), start);
+    /// assert!(action_state.just_pressed(Action::Down));
+    ///
+    /// action_state.tick(start + Duration::from_millis(350), start + Duration::from_millis(300));
+    /// assert!(!action_state.just_pressed(Action::Down));
+    ///
+    /// action_state.tick(start + Duration::from_millis(380), start + Duration::from_millis(350));
+    /// assert!(action_state.just_pressed(Action::Down));
+    /// ```
+    pub fn set_repeat(&mut self, action: A, repeat: Option<RepeatSettings>) {
+        self.settings[action.index()].repeat = repeat;
+    }
+
+    /// How is the `action` repeatedly pressed while it is held, if at all?
+    ///
+    /// See [`ActionState::set_repeat`] for more details.
+    #[must_use]
+    pub fn repeat(&self, action: A) -> Option<RepeatSettings> {
+        self.settings[action.index()].repeat
+    }
+
+    /// How many times has the `action` been repeated since it was last pressed?
+    ///
+    /// See [`ActionState::set_repeat`] for more details.
+    #[must_use]
+    pub fn repeats(&self, action: A) -> u32 {
+        self.tracking[action.index()].repeats
+    }
+
+    /// Sets how the `action` charges up while it is held, or stops charging it if `charge` is [`None`]
+    ///
+    /// Charged actions report how far they have been charged as a fraction between `0.0` and `1.0`,
+    /// based on their [`current_duration`](ActionState::current_duration) while held,
+    /// as is expected for drawing a bow or winding up an attack.
+    /// Use [`ActionState::charge_fraction`] while the action is held,
+    /// and [`ActionState::released_charge`] to read the final charge on the frame it is released.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::action_state::ChargeSettings;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Action {
+    ///     DrawBow,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// action_state.set_charge(Action::DrawBow, Some(ChargeSettings::new(Duration::from_secs(2))));
+    ///
+    /// let start = Instant::now();
+    /// action_state.press(Action::DrawBow);
+    /// action_state.tick(start, start);
+    /// action_state.tick(start + Duration::from_millis(500), start);
+    /// assert_eq!(action_state.charge_fraction(Action::DrawBow), 0.25);
+    /// assert_eq!(action_state.released_charge(Action::DrawBow), None);
+    ///
+    /// action_state.release(Action::DrawBow);
+    /// assert_eq!(action_state.charge_fraction(Action::DrawBow), 0.0);
+    /// assert_eq!(action_state.released_charge(Action::DrawBow), Some(0.25));
+    /// ```
+    pub fn set_charge(&mut self, action: A, charge: Option<ChargeSettings>) {
+        self.settings[action.index()].charge = charge;
+    }
+
+    /// How does the `action` charge up while it is held, if at all?
+    ///
+    /// See [`ActionState::set_charge`] for more details.
+    #[must_use]
+    pub fn charge(&self, action: A) -> Option<ChargeSettings> {
+        self.settings[action.index()].charge
+    }
+
+    /// How far has the `action` been charged, from `0.0` to `1.0`?
+    ///
+    /// This is `0.0` if the action is released, or does not charge.
+    /// See [`ActionState::set_charge`] for more details.
+    #[must_use]
+    pub fn charge_fraction(&self, action: A) -> f32 {
+        let action_data = &self.action_data[action.index()];
+        match self.settings[action.index()].charge {
+            Some(charge) if action_data.state.pressed() => {
+                charge.fraction_after(action_data.timing.current_duration)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// How far was the `action` charged when it was released, from `0.0` to `1.0`?
+    ///
+    /// This is only [`Some`] on the frame that a charged action is [`just_released`](ActionState::just_released).
+    /// Consuming an action cancels its charge, so this is [`None`] once it has been consumed.
+    /// See [`ActionState::set_charge`] for more details.
+    #[must_use]
+    pub fn released_charge(&self, action: A) -> Option<f32> {
+        let action_data = &self.action_data[action.index()];
+        match self.settings[action.index()].charge {
+            Some(charge) if action_data.state.just_released() && !action_data.consumed => {
+                Some(charge.fraction_after(action_data.timing.previous_duration))
+            }
+            _ => None,
+        }
+    }
+
     /// Releases all actions
     pub fn release_all(&mut self) {
         for action in A::variants() {
@@ -490,10 +930,26 @@ impl<A: Actionlike> ActionState<A> {
     }
 }
 
+/// Panics if the `action` is declared to be of a kind other than `expected`
+#[track_caller]
+fn assert_kind<A: Actionlike>(action: &A, expected: ActionKind) {
+    if let Some(kind) = action.kind() {
+        assert_eq!(
+            kind,
+            expected,
+            "the action at index {} is of kind {kind:?}, but was read as {expected:?}",
+            action.index()
+        );
+    }
+}
+
 impl<A: Actionlike> Default for ActionState<A> {
     fn default() -> ActionState<A> {
         ActionState {
-            action_data: A::variants().map(|_| ActionData::default()).collect(),
+            // Dynamic action types may register more actions after the state is created
+            action_data: vec![ActionData::default(); A::N_VARIANTS],
+            settings: default_settings::<A>(),
+            tracking: default_tracking::<A>(),
             _phantom: PhantomData::default(),
         }
     }
@@ -596,6 +1052,116 @@ impl Timing {
     }
 }
 
+/// Configures how an action of an [`ActionState`] responds to its inputs
+///
+/// Unlike [`ActionData`], these are not part of the state of the action:
+/// they are not serialized, sent in [`ActionDiff`]s or replaced by [`ActionState::set_action_data`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub struct ActionSettings {
+    /// Is this action toggled on by one press of its inputs and off by the next, rather than held?
+    ///
+    /// See [`ActionState::set_toggle`] for more details.
+    pub toggle: bool,
+    /// How is this action repeatedly pressed while it is held, if at all?
+    ///
+    /// See [`ActionState::set_repeat`] for more details.
+    pub repeat: Option<RepeatSettings>,
+    /// How long must this action be held to be fully charged, if it charges at all?
+    ///
+    /// See [`ActionState::set_charge`] for more details.
+    pub charge: Option<ChargeSettings>,
+}
+
+/// Bookkeeping about the inputs of an action of an [`ActionState`], kept between updates
+///
+/// This is used to carry out the [`ActionSettings`] of the action, rather than being part of its state.
+#[derive(Debug, Default, Clone, PartialEq, Reflect, FromReflect)]
+struct ActionTracking {
+    /// Were the inputs of this action pressed during the last [`ActionState::update`]?
+    ///
+    /// This is used to detect new presses of toggled actions, whose `state` does not follow their inputs.
+    inputs_pressed: bool,
+    /// How many times has this action been repeated since it was pressed?
+    repeats: u32,
+    /// The [`TriggerMode`] of the held bindings that will press this action once they are released, if any
+    ///
+    /// See [`ActionState::update_held_triggers`] for more details.
+    held_trigger: Option<TriggerMode>,
+    /// How long have the bindings stored in `held_trigger` been held?
+    ///
+    /// Once they are released, [`Timing::previous_duration`] is compared to the duration of [`TriggerMode::Click`].
+    held_timing: Timing,
+    /// Was this action consumed by [`ActionState::consume`] while the bindings stored in `held_trigger` were held?
+    ///
+    /// If so, releasing those bindings does not press the action.
+    /// Unlike [`ActionData::consumed`], this is not cleared when the action is released, only once the bindings are.
+    cancelled: bool,
+    /// Were the bindings stored in `held_trigger` just released, so that the next [`ActionState::update`] presses this action?
+    triggered: bool,
+}
+
+/// Controls how an action is repeatedly pressed while it is held
+///
+/// See [`ActionState::set_repeat`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct RepeatSettings {
+    /// How long the action must be held before it is first repeated
+    pub delay: Duration,
+    /// How long to wait between each following repeat
+    ///
+    /// If this is zero, the action is only repeated once.
+    pub interval: Duration,
+}
+
+impl RepeatSettings {
+    /// Creates settings that first repeat after `delay`, and then after every `interval`
+    #[must_use]
+    pub fn new(delay: Duration, interval: Duration) -> Self {
+        Self { delay, interval }
+    }
+
+    /// How many times should an action that has been held for `duration` have been repeated?
+    #[must_use]
+    pub fn repeats_after(&self, duration: Duration) -> u32 {
+        if duration < self.delay {
+            0
+        } else if self.interval.is_zero() {
+            1
+        } else {
+            1 + ((duration - self.delay).as_nanos() / self.interval.as_nanos()) as u32
+        }
+    }
+}
+
+/// Controls how an action charges up while it is held
+///
+/// See [`ActionState::set_charge`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct ChargeSettings {
+    /// How long the action must be held to be fully charged
+    ///
+    /// If this is zero, the action is fully charged as soon as it is pressed.
+    pub duration: Duration,
+}
+
+impl ChargeSettings {
+    /// Creates settings that are fully charged once the action has been held for `duration`
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+
+    /// How far is an action that has been held for `held` charged, from `0.0` to `1.0`?
+    #[must_use]
+    pub fn fraction_after(&self, held: Duration) -> f32 {
+        if self.duration.is_zero() {
+            1.0
+        } else {
+            (held.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        }
+    }
+}
+
 /// Stores presses and releases of buttons without timing information
 ///
 /// These are typically accessed using the `Events<ActionDiff>` resource.
@@ -621,6 +1187,75 @@ pub enum ActionDiff<A: Actionlike, ID: Eq + Clone + Component> {
     },
 }
 
+/// A change to the state of an action, as produced by [`ActionState::update_with_events`]
+///
+/// These are sent as events by [`update_action_state`](crate::systems::update_action_state)
+/// when enabled using [`InputManagerPlugin::with_action_events`](crate::plugin::InputManagerPlugin::with_action_events),
+/// allowing systems to respond to actions without polling every [`ActionState`].
+///
+/// `entity` is the entity whose [`ActionState`] changed, or [`None`] if it is stored as a resource.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActionEvent<A: Actionlike> {
+    /// The action was pressed
+    Pressed {
+        /// The entity whose action was pressed
+        entity: Option<Entity>,
+        /// The action that was pressed
+        action: A,
+    },
+    /// The action was released
+    Released {
+        /// The entity whose action was released
+        entity: Option<Entity>,
+        /// The action that was released
+        action: A,
+        /// How long the action was held before it was released
+        duration: Duration,
+    },
+    /// The action was pressed before this update, and is still pressed
+    Held {
+        /// The entity whose action is held
+        entity: Option<Entity>,
+        /// The action that is held
+        action: A,
+        /// How long the action has been held
+        duration: Duration,
+    },
+    /// The value of the action changed
+    ValueChanged {
+        /// The entity whose action changed
+        entity: Option<Entity>,
+        /// The action whose value changed
+        action: A,
+        /// The new value of the action
+        value: f32,
+    },
+}
+
+impl<A: Actionlike> ActionEvent<A> {
+    /// The entity whose [`ActionState`] changed, or [`None`] if it is stored as a resource
+    #[must_use]
+    pub fn entity(&self) -> Option<Entity> {
+        match self {
+            ActionEvent::Pressed { entity, .. }
+            | ActionEvent::Released { entity, .. }
+            | ActionEvent::Held { entity, .. }
+            | ActionEvent::ValueChanged { entity, .. } => *entity,
+        }
+    }
+
+    /// The action that changed
+    #[must_use]
+    pub fn action(&self) -> &A {
+        match self {
+            ActionEvent::Pressed { action, .. }
+            | ActionEvent::Released { action, .. }
+            | ActionEvent::Held { action, .. }
+            | ActionEvent::ValueChanged { action, .. } => action,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate as leafwing_input_manager;
@@ -634,6 +1269,59 @@ mod tests {
         Hide,
     }
 
+    #[test]
+    fn typed_accessors() {
+        use crate::action_state::ActionState;
+        use crate::axislike::DualAxisData;
+
+        #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+        enum KindedAction {
+            #[actionlike(kind = "Axis")]
+            Throttle,
+            #[actionlike(kind = "DualAxis")]
+            Move,
+        }
+
+        let mut action_state = ActionState::<KindedAction>::default();
+        assert_eq!(action_state.axis_value(KindedAction::Throttle), 0.0);
+        assert_eq!(
+            action_state.dual_axis_pair(KindedAction::Move),
+            DualAxisData::default()
+        );
+
+        action_state.action_data_mut(KindedAction::Throttle).value = 2.0;
+        action_state.action_data_mut(KindedAction::Move).axis_pair =
+            Some(DualAxisData::new(0.5, -3.0));
+        assert_eq!(action_state.axis_value(KindedAction::Throttle), 1.0);
+        assert_eq!(
+            action_state.dual_axis_pair(KindedAction::Move),
+            DualAxisData::new(0.5, -1.0)
+        );
+
+        // Actions whose kind is not declared can be read either way
+        let action_state = ActionState::<Action>::default();
+        assert_eq!(action_state.axis_value(Action::Run), 0.0);
+        assert_eq!(
+            action_state.dual_axis_pair(Action::Run),
+            DualAxisData::default()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_accessor() {
+        use crate::action_state::ActionState;
+
+        #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+        enum KindedAction {
+            #[actionlike(kind = "DualAxis")]
+            Move,
+        }
+
+        let action_state = ActionState::<KindedAction>::default();
+        let _ = action_state.axis_value(KindedAction::Move);
+    }
+
     #[test]
     fn press_lifecycle() {
         use crate::action_state::ActionState;
@@ -707,6 +1395,187 @@ mod tests {
         assert!(!action_state.just_released(Action::Run));
     }
 
+    #[test]
+    fn buffered_updates() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::buttonlike::ButtonState;
+        use crate::Actionlike;
+        use bevy::utils::Instant;
+
+        let mut pressed = vec![ActionData::default(); Action::N_VARIANTS];
+        pressed[Action::Run.index()].state = ButtonState::JustPressed;
+        let released = vec![ActionData::default(); Action::N_VARIANTS];
+
+        // Regular updates release actions straight away
+        let mut action_state = ActionState::<Action>::default();
+        action_state.update(pressed.clone());
+        action_state.update(released.clone());
+        assert!(action_state.just_released(Action::Run));
+
+        // Buffered updates keep them until they are ticked
+        let mut action_state = ActionState::<Action>::default();
+        action_state.update_buffered(pressed, None, |_| ());
+        action_state.update_buffered(released.clone(), None, |_| ());
+        assert!(action_state.just_pressed(Action::Run));
+
+        action_state.tick(Instant::now(), Instant::now());
+        action_state.update_buffered(released, None, |_| ());
+        assert!(action_state.just_released(Action::Run));
+    }
+
+    #[test]
+    fn settings_are_not_state() {
+        use crate::action_state::{ActionData, ActionState, ChargeSettings, RepeatSettings};
+        use bevy::utils::Duration;
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.set_toggle(Action::Hide, true);
+        action_state.set_repeat(
+            Action::Run,
+            Some(RepeatSettings::new(
+                Duration::from_millis(300),
+                Duration::ZERO,
+            )),
+        );
+        action_state.set_charge(Action::Jump, Some(ChargeSettings::new(Duration::ZERO)));
+        let settings = action_state.action_settings(Action::Run);
+
+        // Replacing the state of actions keeps their settings
+        action_state.set_action_data(Action::Hide, ActionData::default());
+        action_state.set_action_data(Action::Run, ActionData::default());
+        assert!(action_state.is_toggle(Action::Hide));
+        assert_eq!(action_state.action_settings(Action::Run), settings);
+
+        // Settings are not serialized
+        let serialized = ron::to_string(&action_state).unwrap();
+        let mut deserialized: ActionState<Action> = ron::from_str(&serialized).unwrap();
+        assert!(!deserialized.is_toggle(Action::Hide));
+        assert_eq!(deserialized.repeat(Action::Run), None);
+        assert_eq!(deserialized.charge(Action::Jump), None);
+
+        deserialized.set_action_settings(Action::Run, settings);
+        assert_eq!(deserialized.action_settings(Action::Run), settings);
+    }
+
+    #[test]
+    fn toggle_lifecycle() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::buttonlike::ButtonState;
+        use crate::Actionlike;
+        use bevy::utils::Instant;
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.set_toggle(Action::Hide, true);
+        assert!(action_state.is_toggle(Action::Hide));
+        assert!(!action_state.is_toggle(Action::Run));
+
+        let mut pressed = vec![ActionData::default(); Action::N_VARIANTS];
+        pressed[Action::Hide.index()].state = ButtonState::JustPressed;
+        pressed[Action::Run.index()].state = ButtonState::JustPressed;
+        let released = vec![ActionData::default(); Action::N_VARIANTS];
+
+        // Toggled on by the first press
+        action_state.update(pressed.clone());
+        assert!(action_state.just_pressed(Action::Hide));
+        action_state.tick(Instant::now(), Instant::now());
+
+        // Held inputs and released inputs leave it on
+        action_state.update(pressed.clone());
+        assert!(action_state.pressed(Action::Hide));
+        action_state.update(released.clone());
+        assert!(action_state.pressed(Action::Hide));
+        assert!(action_state.released(Action::Run));
+
+        // Toggled off by the next press
+        action_state.update(pressed.clone());
+        assert!(action_state.just_released(Action::Hide));
+        assert!(action_state.pressed(Action::Run));
+        action_state.tick(Instant::now(), Instant::now());
+        action_state.update(released.clone());
+        assert!(action_state.released(Action::Hide));
+
+        // Consumed toggled actions can be pressed again after their inputs are released
+        action_state.update(pressed.clone());
+        action_state.consume(Action::Hide);
+        action_state.tick(Instant::now(), Instant::now());
+        action_state.update(released);
+        action_state.update(pressed);
+        assert!(action_state.just_pressed(Action::Hide));
+    }
+
+    #[test]
+    fn repeats_after() {
+        use crate::action_state::RepeatSettings;
+        use bevy::utils::Duration;
+
+        let repeat = RepeatSettings::new(Duration::from_millis(300), Duration::from_millis(80));
+        assert_eq!(repeat.repeats_after(Duration::ZERO), 0);
+        assert_eq!(repeat.repeats_after(Duration::from_millis(299)), 0);
+        assert_eq!(repeat.repeats_after(Duration::from_millis(300)), 1);
+        assert_eq!(repeat.repeats_after(Duration::from_millis(379)), 1);
+        assert_eq!(repeat.repeats_after(Duration::from_millis(380)), 2);
+        assert_eq!(repeat.repeats_after(Duration::from_millis(1000)), 9);
+
+        let once = RepeatSettings::new(Duration::from_millis(300), Duration::ZERO);
+        assert_eq!(once.repeats_after(Duration::from_secs(10)), 1);
+    }
+
+    #[test]
+    fn charge_lifecycle() {
+        use crate::action_state::{ActionState, ChargeSettings};
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.set_charge(
+            Action::Jump,
+            Some(ChargeSettings::new(Duration::from_secs(1))),
+        );
+        let start = Instant::now();
+
+        // Actions that do not charge never report a charge
+        action_state.press(Action::Run);
+        action_state.tick(start, start);
+        action_state.tick(start + Duration::from_secs(2), start);
+        assert_eq!(action_state.charge_fraction(Action::Run), 0.0);
+        action_state.release(Action::Run);
+        assert_eq!(action_state.released_charge(Action::Run), None);
+
+        // Charges are capped once the action is fully charged
+        action_state.press(Action::Jump);
+        action_state.tick(start, start);
+        assert_eq!(action_state.charge_fraction(Action::Jump), 0.0);
+        action_state.tick(start + Duration::from_secs(3), start);
+        assert_eq!(action_state.charge_fraction(Action::Jump), 1.0);
+        action_state.release(Action::Jump);
+        assert_eq!(action_state.released_charge(Action::Jump), Some(1.0));
+
+        // The released charge is only available for a single frame
+        action_state.tick(start + Duration::from_secs(3), start);
+        assert_eq!(action_state.released_charge(Action::Jump), None);
+
+        // Consuming the action cancels its charge
+        action_state.press(Action::Jump);
+        action_state.tick(start, start);
+        action_state.tick(start + Duration::from_millis(500), start);
+        action_state.consume(Action::Jump);
+        assert_eq!(action_state.charge_fraction(Action::Jump), 0.0);
+        assert_eq!(action_state.released_charge(Action::Jump), None);
+    }
+
+    #[test]
+    fn fraction_after() {
+        use crate::action_state::ChargeSettings;
+        use bevy::utils::Duration;
+
+        let charge = ChargeSettings::new(Duration::from_millis(800));
+        assert_eq!(charge.fraction_after(Duration::ZERO), 0.0);
+        assert_eq!(charge.fraction_after(Duration::from_millis(200)), 0.25);
+        assert_eq!(charge.fraction_after(Duration::from_secs(5)), 1.0);
+
+        let instant = ChargeSettings::new(Duration::ZERO);
+        assert_eq!(instant.fraction_after(Duration::ZERO), 1.0);
+    }
+
     #[test]
     fn time_tick_ticks_away() {
         use crate::action_state::ActionState;
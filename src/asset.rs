@@ -0,0 +1,327 @@
+//! Loading [`InputMap`]s from asset files, and keeping entities in sync with them as they change
+//!
+//! This module is only available with the `asset` feature enabled.
+//!
+//! Add an [`InputMapAssetPlugin`] to your app, then add a [`Handle<InputMap<A>>`] to your entities.
+//! Once the asset has loaded, a matching [`InputMap`] component will be inserted on the entity,
+//! and it will be updated every time the asset changes.
+//! If [`AssetPlugin::watch_for_changes`](bevy::asset::AssetPlugin::watch_for_changes) is set,
+//! this includes edits made to the file on disk while your game is running.
+//!
+//! Files are parsed as JSON if their extension is `json`, and as RON otherwise.
+//! The expected layout is the same as the [`Serialize`](serde::Serialize) implementation of [`InputMap`]:
+//!
+//! ```ron
+//! (
+//!     map: {
+//!         Jump: [Single(Keyboard(Space)), Single(GamepadButton(South))],
+//!         Run: [Single(Keyboard(LShift))],
+//!     },
+//!     // Settings are optional, and are keyed by action
+//!     aggregation: {
+//!         Run: MaxMagnitude,
+//!     },
+//! )
+//! ```
+//!
+//! When an asset is reloaded, the bindings and settings of the entity's [`InputMap`] are replaced by those in the file.
+//! The associated gamepad is kept, as are any settings that the file leaves at their defaults,
+//! so settings configured in code survive hot reloads.
+
+use crate::input_map::InputMap;
+use crate::plugin::InputManagerSystem;
+use crate::Actionlike;
+
+use bevy::app::{App, CoreStage, Plugin};
+use bevy::asset::{
+    AddAsset, AssetEvent, AssetLoader, Assets, BoxedFuture, Error, Handle, LoadContext, LoadedAsset,
+};
+use bevy::ecs::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashSet;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use serde::de::DeserializeOwned;
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// A [`Plugin`] that loads [`InputMap<A>`] assets and keeps the [`InputMap`] components of entities in sync with them
+///
+/// Your `A` type must implement [`TypeUuid`], so that the input maps of different action types are stored separately.
+///
+/// By default, files ending in `.input.ron` or `.input.json` are loaded as [`InputMap<A>`].
+/// As assets are matched to loaders by their extension alone,
+/// each action type needs its own extensions: use [`InputMapAssetPlugin::with_extensions`] if you have more than one.
+///
+/// ## Systems
+///
+/// - [`sync_input_map_assets`], which updates the [`InputMap`] of each entity from its [`Handle<InputMap<A>>`]
+///     - runs during [`CoreStage::PreUpdate`], before [`InputManagerSystem::Update`]
+pub struct InputMapAssetPlugin<A: Actionlike> {
+    extensions: &'static [&'static str],
+    _phantom: PhantomData<A>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Actionlike> Default for InputMapAssetPlugin<A> {
+    fn default() -> Self {
+        Self::with_extensions(&["input.ron", "input.json"])
+    }
+}
+
+impl<A: Actionlike> InputMapAssetPlugin<A> {
+    /// Creates a version of the plugin that loads files with the provided `extensions`, without the preceding dot
+    ///
+    /// Extensions may contain dots, such as `"player.ron"`.
+    #[must_use]
+    pub fn with_extensions(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A> Plugin for InputMapAssetPlugin<A>
+where
+    A: Actionlike + TypeUuid + DeserializeOwned + Eq + Hash,
+{
+    fn build(&self, app: &mut App) {
+        app.add_asset::<InputMap<A>>()
+            .add_asset_loader(InputMapLoader::<A>::new(self.extensions))
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                sync_input_map_assets::<A>.before(InputManagerSystem::Update),
+            );
+    }
+}
+
+/// An [`AssetLoader`] for [`InputMap<A>`] files stored as RON or JSON
+///
+/// This is added to your app by the [`InputMapAssetPlugin`].
+pub struct InputMapLoader<A: Actionlike> {
+    extensions: &'static [&'static str],
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> InputMapLoader<A> {
+    /// Creates a loader for files with the provided `extensions`, without the preceding dot
+    #[must_use]
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A> AssetLoader for InputMapLoader<A>
+where
+    A: Actionlike + TypeUuid + DeserializeOwned + Eq + Hash,
+{
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let input_map = deserialize_input_map::<A>(bytes, load_context.path())?;
+            load_context.set_default_asset(LoadedAsset::new(input_map));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
+
+/// Parses `bytes` as JSON if the extension of `path` is `json`, and as RON otherwise
+fn deserialize_input_map<A>(bytes: &[u8], path: &Path) -> Result<InputMap<A>, Error>
+where
+    A: Actionlike + DeserializeOwned + Eq + Hash,
+{
+    Ok(if path.extension() == Some(OsStr::new("json")) {
+        serde_json::from_slice(bytes)?
+    } else {
+        ron::de::from_bytes(bytes)?
+    })
+}
+
+/// Updates the [`InputMap`] of each entity to match the asset stored in its [`Handle<InputMap<A>>`]
+///
+/// The [`InputMap`] is replaced when the handle is added or changed, and whenever the asset is modified.
+/// If the entity does not yet have an [`InputMap`], one is inserted.
+///
+/// Gamepads are not stored in asset files, so the gamepad associated with the entity's [`InputMap`] is kept.
+/// Removing the handle leaves the current [`InputMap`] in place.
+pub fn sync_input_map_assets<A: Actionlike + TypeUuid>(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<InputMap<A>>>,
+    assets: Res<Assets<InputMap<A>>>,
+    mut query: Query<(
+        Entity,
+        &Handle<InputMap<A>>,
+        ChangeTrackers<Handle<InputMap<A>>>,
+        Option<&mut InputMap<A>>,
+    )>,
+) {
+    let modified_assets: HashSet<_> = asset_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle.id()),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+
+    for (entity, handle, handle_tracker, maybe_input_map) in query.iter_mut() {
+        if !handle_tracker.is_changed() && !modified_assets.contains(&handle.id()) {
+            continue;
+        }
+
+        // If the asset is still loading, we'll be notified once it's created
+        if let Some(loaded_input_map) = assets.get(handle) {
+            match maybe_input_map {
+                Some(mut input_map) => {
+                    let previous_input_map =
+                        std::mem::replace(&mut *input_map, loaded_input_map.clone());
+                    if let Some(gamepad) = previous_input_map.gamepad() {
+                        input_map.set_gamepad(gamepad);
+                    }
+                    input_map.fill_settings_from(&previous_input_map);
+                }
+                None => {
+                    commands.entity(entity).insert(loaded_input_map.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::asset::AssetPlugin;
+    use bevy::input::gamepad::Gamepad;
+    use bevy::input::keyboard::KeyCode;
+    use bevy::prelude::MinimalPlugins;
+    use serde::Deserialize;
+
+    #[derive(Actionlike, TypeUuid, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[uuid = "6E3A1D0E-56AF-4B62-9C9E-0D8F6C45B3A7"]
+    enum Action {
+        Run,
+        Jump,
+    }
+
+    #[test]
+    fn deserialize_by_extension() {
+        let expected = InputMap::new([
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::LShift, Action::Run),
+        ]);
+
+        let ron = b"(map: {Jump: [Single(Keyboard(Space))], Run: [Single(Keyboard(LShift))]})";
+        let from_ron = deserialize_input_map::<Action>(ron, Path::new("player.input.ron")).unwrap();
+        assert_eq!(from_ron, expected);
+
+        let json = br#"{"map": {"Jump": [{"Single": {"Keyboard": "Space"}}], "Run": [{"Single": {"Keyboard": "LShift"}}]}}"#;
+        let from_json =
+            deserialize_input_map::<Action>(json, Path::new("player.input.json")).unwrap();
+        assert_eq!(from_json, expected);
+
+        assert!(deserialize_input_map::<Action>(json, Path::new("player.input.ron")).is_err());
+    }
+
+    #[test]
+    fn entities_track_their_asset() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .add_plugin(InputMapAssetPlugin::<Action>::default());
+
+        let handle = app
+            .world
+            .resource_mut::<Assets<InputMap<Action>>>()
+            .add(InputMap::new([(KeyCode::Space, Action::Jump)]));
+        let entity = app.world.spawn(handle.clone()).id();
+
+        app.update();
+        assert_eq!(
+            app.world.get::<InputMap<Action>>(entity),
+            Some(&InputMap::new([(KeyCode::Space, Action::Jump)]))
+        );
+
+        // Players may have been assigned a gamepad since the asset was loaded
+        let gamepad = Gamepad { id: 1 };
+        app.world
+            .get_mut::<InputMap<Action>>(entity)
+            .unwrap()
+            .set_gamepad(gamepad);
+
+        app.world
+            .resource_mut::<Assets<InputMap<Action>>>()
+            .get_mut(&handle)
+            .unwrap()
+            .insert(KeyCode::LShift, Action::Run);
+
+        // Asset events are sent at the end of the frame, and processed at the start of the next
+        app.update();
+        app.update();
+
+        let input_map = app.world.get::<InputMap<Action>>(entity).unwrap();
+        assert_eq!(input_map.get(Action::Run).len(), 1);
+        assert_eq!(input_map.get(Action::Jump).len(), 1);
+        assert_eq!(input_map.gamepad(), Some(gamepad));
+    }
+
+    #[test]
+    fn settings_survive_reloads() {
+        use crate::user_input::{AggregationPolicy, TriggerMode};
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .add_plugin(InputMapAssetPlugin::<Action>::default());
+
+        let ron = b"(map: {Jump: [Single(Keyboard(Space))]}, triggers: {Jump: [(Single(Keyboard(Space)), Release)]})";
+        let loaded = deserialize_input_map::<Action>(ron, Path::new("player.input.ron")).unwrap();
+        assert_eq!(
+            loaded.trigger(Action::Jump, &KeyCode::Space.into()),
+            TriggerMode::Release
+        );
+
+        let handle = app
+            .world
+            .resource_mut::<Assets<InputMap<Action>>>()
+            .add(loaded);
+        let entity = app.world.spawn(handle.clone()).id();
+        app.update();
+
+        // Settings configured in code, which the file does not set
+        app.world
+            .get_mut::<InputMap<Action>>(entity)
+            .unwrap()
+            .set_aggregation(Action::Run, AggregationPolicy::MaxMagnitude);
+
+        app.world
+            .resource_mut::<Assets<InputMap<Action>>>()
+            .get_mut(&handle)
+            .unwrap()
+            .insert(KeyCode::LShift, Action::Run);
+        app.update();
+        app.update();
+
+        let input_map = app.world.get::<InputMap<Action>>(entity).unwrap();
+        assert_eq!(input_map.get(Action::Run).len(), 1);
+        assert_eq!(
+            input_map.aggregation(Action::Run),
+            AggregationPolicy::MaxMagnitude
+        );
+        assert_eq!(
+            input_map.trigger(Action::Jump, &KeyCode::Space.into()),
+            TriggerMode::Release
+        );
+    }
+}
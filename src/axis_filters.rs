@@ -0,0 +1,303 @@
+//! Temporal filters that smooth or ramp the values of axis-like bindings
+//!
+//! Raw mouse motion and analog sticks can be jittery, and stick-driven cameras often want to speed up gradually.
+//! Each binding in an [`InputMap`](crate::input_map::InputMap) can be given a chain of [`AxisFilter`]s
+//! using [`InputMap::set_filters`](crate::input_map::InputMap::set_filters),
+//! which are applied in order to the `value` and `axis_pair` of that binding before they are combined into its action.
+//!
+//! Filters keep running once a binding is released, so the `value` and `axis_pair` of its action settle back to zero
+//! as smoothly as they rose, even though the action itself is released straight away.
+//!
+//! Filters depend on the values of previous frames, which are stored in an [`AxisFilterState`]
+//! alongside each [`InputMap`](crate::input_map::InputMap).
+//! The [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) adds it as a resource,
+//! and [`update_action_state`](crate::systems::update_action_state) adds it as a component
+//! to entities whose bindings are filtered the first time they are updated.
+//! Use [`AxisFilterState::reset`] to make the filters of an entity start again from zero.
+//!
+//! # Example
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::axis_filters::AxisFilter;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+//! enum CameraAction {
+//!     Look,
+//!     Turn,
+//! }
+//!
+//! let mut input_map = InputMap::default();
+//! input_map
+//!     .insert(DualAxis::right_stick(), CameraAction::Look)
+//!     .set_filters(
+//!         CameraAction::Look,
+//!         DualAxis::right_stick(),
+//!         [AxisFilter::Exponential { half_life: Duration::from_millis(30) }],
+//!     )
+//!     .insert(SingleAxis::symmetric(GamepadAxisType::RightStickX, 0.1), CameraAction::Turn)
+//!     .set_filters(
+//!         CameraAction::Turn,
+//!         SingleAxis::symmetric(GamepadAxisType::RightStickX, 0.1),
+//!         [
+//!             AxisFilter::Acceleration { ramp: Duration::from_millis(400) },
+//!             AxisFilter::MaxRate { time_per_unit: Duration::from_millis(100) },
+//!         ],
+//!     );
+//! ```
+
+use crate::axislike::DualAxisData;
+use crate::user_input::UserInput;
+use crate::Actionlike;
+
+use bevy::ecs::{component::Component, system::Resource};
+use bevy::math::Vec2;
+use bevy::utils::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// A temporal filter applied to the `value` and `axis_pair` of a single binding
+///
+/// See the [module-level documentation](crate::axis_filters) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AxisFilter {
+    /// Exponentially smooths the input, removing jitter at the cost of some latency
+    ///
+    /// The gap between the filtered and the raw input halves every `half_life`.
+    Exponential {
+        /// How long it takes to close half of the gap to the raw input
+        half_life: Duration,
+    },
+    /// Scales the input up from zero while it is held, so that small corrections are precise and long turns are fast
+    ///
+    /// The scale grows linearly from `0.0` to `1.0` over `ramp`, and is reset once the input returns to zero.
+    Acceleration {
+        /// How long the input must be held to reach its full value
+        ramp: Duration,
+    },
+    /// Limits how quickly the input can change
+    ///
+    /// The input changes by at most `1.0` every `time_per_unit`.
+    /// For axis pairs, this limits the length of the change.
+    MaxRate {
+        /// How long it takes to change the input by `1.0`
+        time_per_unit: Duration,
+    },
+}
+
+impl AxisFilter {
+    /// Filters the `target` input, given the `memory` of this filter from the previous frame
+    fn apply(&self, target: FilterMemory, memory: &mut FilterMemory, delta: Duration) {
+        match *self {
+            AxisFilter::Exponential { half_life } => {
+                let factor = if half_life.is_zero() {
+                    0.0
+                } else {
+                    0.5_f32.powf(delta.as_secs_f32() / half_life.as_secs_f32())
+                };
+                memory.value = target.value + (memory.value - target.value) * factor;
+                memory.xy = target.xy + (memory.xy - target.xy) * factor;
+            }
+            AxisFilter::Acceleration { ramp } => {
+                if target.value == 0.0 && target.xy == Vec2::ZERO {
+                    memory.held = Duration::ZERO;
+                } else {
+                    memory.held += delta;
+                }
+
+                let scale = if ramp.is_zero() {
+                    1.0
+                } else {
+                    (memory.held.as_secs_f32() / ramp.as_secs_f32()).min(1.0)
+                };
+                memory.value = target.value * scale;
+                memory.xy = target.xy * scale;
+            }
+            AxisFilter::MaxRate { time_per_unit } => {
+                if time_per_unit.is_zero() {
+                    memory.value = target.value;
+                    memory.xy = target.xy;
+                    return;
+                }
+
+                let max_change = delta.as_secs_f32() / time_per_unit.as_secs_f32();
+                memory.value += (target.value - memory.value).clamp(-max_change, max_change);
+                memory.xy += (target.xy - memory.xy).clamp_length_max(max_change);
+            }
+        }
+    }
+}
+
+/// The output of a single [`AxisFilter`] on the previous frame
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct FilterMemory {
+    value: f32,
+    xy: Vec2,
+    /// How long the input has been away from zero, used by [`AxisFilter::Acceleration`]
+    held: Duration,
+}
+
+/// The previous outputs of the [`AxisFilter`]s of each binding in an [`InputMap`](crate::input_map::InputMap)
+///
+/// See the [module-level documentation](crate::axis_filters) for more information.
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct AxisFilterState<A: Actionlike> {
+    /// The memory of each filter of each binding, keyed in the same way as the filters of the input map
+    bindings: HashMap<(usize, UserInput), Vec<FilterMemory>>,
+    _phantom: PhantomData<A>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Actionlike> Default for AxisFilterState<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> AxisFilterState<A> {
+    /// Forgets the previous values of every binding, so that filters start again from zero
+    ///
+    /// This is useful when teleporting the camera, or switching between control schemes.
+    pub fn reset(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Forgets the previous values of the bindings of `action`, so that their filters start again from zero
+    pub fn reset_action(&mut self, action: A) {
+        let action_index = action.index();
+        self.bindings.retain(|(index, _), _| *index != action_index);
+    }
+
+    /// Applies the `filters` of the binding between `input` and the action at `action_index`,
+    /// advancing them by `delta`
+    pub(crate) fn apply(
+        &mut self,
+        action_index: usize,
+        input: &UserInput,
+        filters: &[AxisFilter],
+        value: f32,
+        axis_pair: Option<DualAxisData>,
+        delta: Duration,
+    ) -> (f32, Option<DualAxisData>) {
+        let memories = self
+            .bindings
+            .entry((action_index, input.clone()))
+            .or_default();
+        memories.resize(filters.len(), FilterMemory::default());
+
+        let mut filtered = FilterMemory {
+            value,
+            xy: axis_pair
+                .map(|axis_pair| axis_pair.xy())
+                .unwrap_or_default(),
+            held: Duration::ZERO,
+        };
+        for (filter, memory) in filters.iter().zip(memories.iter_mut()) {
+            filter.apply(filtered, memory, delta);
+            filtered = *memory;
+        }
+
+        (
+            filtered.value,
+            axis_pair.map(|_| DualAxisData::from_xy(filtered.xy)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    enum Action {
+        Turn,
+    }
+
+    const FRAME: Duration = Duration::from_millis(100);
+
+    /// Runs the `filter` on a constant `target` for `frames` frames of length [`FRAME`], returning each output value
+    fn filter_values(filter: AxisFilter, target: f32, frames: usize) -> Vec<f32> {
+        let target = FilterMemory {
+            value: target,
+            xy: Vec2::new(target, 0.0),
+            held: Duration::ZERO,
+        };
+        let mut memory = FilterMemory::default();
+
+        (0..frames)
+            .map(|_| {
+                filter.apply(target, &mut memory, FRAME);
+                assert_eq!(memory.value, memory.xy.x);
+                memory.value
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exponential() {
+        let filter = AxisFilter::Exponential { half_life: FRAME };
+        assert_eq!(filter_values(filter, 1.0, 3), [0.5, 0.75, 0.875]);
+
+        let instant = AxisFilter::Exponential {
+            half_life: Duration::ZERO,
+        };
+        assert_eq!(filter_values(instant, 1.0, 1), [1.0]);
+    }
+
+    #[test]
+    fn acceleration() {
+        let filter = AxisFilter::Acceleration { ramp: FRAME * 4 };
+        assert_eq!(
+            filter_values(filter, -1.0, 5),
+            [-0.25, -0.5, -0.75, -1.0, -1.0]
+        );
+
+        // Releasing the input resets the ramp
+        let mut memory = FilterMemory::default();
+        let held = FilterMemory {
+            value: 1.0,
+            ..Default::default()
+        };
+        filter.apply(held, &mut memory, FRAME);
+        filter.apply(FilterMemory::default(), &mut memory, FRAME);
+        filter.apply(held, &mut memory, FRAME);
+        assert_eq!(memory.value, 0.25);
+    }
+
+    #[test]
+    fn max_rate() {
+        let filter = AxisFilter::MaxRate {
+            time_per_unit: FRAME * 2,
+        };
+        assert_eq!(filter_values(filter, 1.2, 4), [0.5, 1.0, 1.2, 1.2]);
+    }
+
+    #[test]
+    fn filters_are_chained_per_binding() {
+        let filters = [
+            AxisFilter::Acceleration { ramp: FRAME * 2 },
+            AxisFilter::MaxRate {
+                time_per_unit: FRAME * 4,
+            },
+        ];
+        let input = UserInput::from(bevy::input::keyboard::KeyCode::Right);
+        let other_input = UserInput::from(bevy::input::keyboard::KeyCode::D);
+        let mut state = AxisFilterState::<Action>::default();
+
+        let mut apply = |input: &UserInput| state.apply(0, input, &filters, 1.0, None, FRAME).0;
+        // Acceleration gives 0.5 then 1.0, which is then limited to change by 0.25 per frame
+        assert_eq!(apply(&input), 0.25);
+        assert_eq!(apply(&input), 0.5);
+        assert_eq!(apply(&other_input), 0.25);
+        assert_eq!(apply(&input), 0.75);
+
+        state.reset_action(Action::Turn);
+        assert_eq!(state.apply(0, &input, &filters, 1.0, None, FRAME).0, 0.25);
+    }
+}
@@ -9,7 +9,7 @@ use bevy::input::{
 };
 use bevy::math::Vec2;
 use bevy::reflect::{FromReflect, Reflect};
-use bevy::utils::FloatOrd;
+use bevy::utils::{Duration, FloatOrd};
 use serde::{Deserialize, Serialize};
 
 /// A single directional axis with a configurable trigger zone.
@@ -134,6 +134,14 @@ impl SingleAxis {
         self.positive_low = deadzone;
         self
     }
+
+    /// Are the thresholds of this axis valid?
+    ///
+    /// `positive_low` must be greater than or equal to `negative_low`, and neither can be NaN.
+    #[must_use]
+    pub fn has_valid_thresholds(&self) -> bool {
+        self.positive_low >= self.negative_low
+    }
 }
 
 impl PartialEq for SingleAxis {
@@ -422,6 +430,115 @@ pub enum MouseMotionAxisType {
     Y,
 }
 
+/// How the [`MouseMotion`](bevy::input::mouse::MouseMotion) deltas of a frame are converted into an axis value
+///
+/// Mouse motion is reported as the distance moved since the last frame,
+/// so the same hand movement produces smaller values at higher frame rates.
+/// Gamepad sticks instead report a position that does not depend on the frame rate,
+/// which makes [`Raw`](MouseMotionNormalization::Raw) mouse motion and sticks feel very different when bound to the same action.
+///
+/// The normalization used by an [`InputMap`](crate::input_map::InputMap) is set with
+/// [`InputMap::set_mouse_motion_normalization`](crate::input_map::InputMap::set_mouse_motion_normalization),
+/// and is applied before the deadzone of each [`SingleAxis`] is checked.
+///
+/// # Example
+/// ```rust
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::axislike::MouseMotionNormalization;
+///
+/// // The mouse moves at 300 pixels per second, sampled at 30 and at 120 frames per second
+/// let slow_frame = Duration::from_secs_f32(1.0 / 30.0);
+/// let fast_frame = Duration::from_secs_f32(1.0 / 120.0);
+/// let (slow_delta, fast_delta) = (10.0, 2.5);
+///
+/// // Raw deltas depend on the frame rate
+/// let raw = MouseMotionNormalization::Raw;
+/// assert_eq!(raw.normalize(slow_delta, slow_frame), 10.0);
+/// assert_eq!(raw.normalize(fast_delta, fast_frame), 2.5);
+///
+/// // Rates do not: 300 pixels per second, scaled by 0.01
+/// let rate = MouseMotionNormalization::Rate { scale: 0.01 };
+/// assert!((rate.normalize(slow_delta, slow_frame) - 3.0).abs() < 1e-4);
+/// assert!((rate.normalize(fast_delta, fast_frame) - 3.0).abs() < 1e-4);
+///
+/// // Stick-like values reach full deflection at 600 pixels per second
+/// let stick = MouseMotionNormalization::Stick { scale: 1.0 / 600.0 };
+/// assert!((stick.normalize(slow_delta, slow_frame) - 0.5).abs() < 1e-4);
+/// assert!((stick.normalize(fast_delta, fast_frame) - 0.5).abs() < 1e-4);
+/// assert_eq!(stick.normalize(-100.0, fast_frame), -1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum MouseMotionNormalization {
+    /// The sum of the deltas of the frame, in pixels
+    #[default]
+    Raw,
+    /// The speed of the mouse in pixels per second, multiplied by `scale`
+    Rate {
+        /// The factor applied to the speed of the mouse
+        scale: f32,
+    },
+    /// The speed of the mouse in pixels per second multiplied by `scale`,
+    /// then clamped like a gamepad stick
+    ///
+    /// The value of each [`SingleAxis`] is clamped to `[-1.0, 1.0]` on its own,
+    /// while the axis pair of a [`DualAxis`] is clamped to a length of `1.0`, so that diagonal motion is not faster.
+    Stick {
+        /// The factor applied to the speed of the mouse, which is the inverse of the speed needed for full deflection
+        scale: f32,
+    },
+}
+
+impl MouseMotionNormalization {
+    /// Converts the total mouse motion `delta` along one axis during a frame lasting `frame_time`
+    ///
+    /// [`Stick`](MouseMotionNormalization::Stick) values are clamped to `[-1.0, 1.0]`, as for a [`SingleAxis`].
+    /// Frames with a `frame_time` of zero are treated as having no motion,
+    /// unless the normalization is [`Raw`](MouseMotionNormalization::Raw).
+    #[must_use]
+    pub fn normalize(&self, delta: f32, frame_time: Duration) -> f32 {
+        let rate = |scale: f32| {
+            if frame_time.is_zero() {
+                0.0
+            } else {
+                delta / frame_time.as_secs_f32() * scale
+            }
+        };
+
+        match *self {
+            MouseMotionNormalization::Raw => delta,
+            MouseMotionNormalization::Rate { scale } => rate(scale),
+            MouseMotionNormalization::Stick { scale } => rate(scale).clamp(-1.0, 1.0),
+        }
+    }
+}
+
+impl PartialEq for MouseMotionNormalization {
+    fn eq(&self, other: &Self) -> bool {
+        use MouseMotionNormalization::*;
+
+        match (self, other) {
+            (Raw, Raw) => true,
+            (Rate { scale: a }, Rate { scale: b }) | (Stick { scale: a }, Stick { scale: b }) => {
+                FloatOrd(*a) == FloatOrd(*b)
+            }
+            _ => false,
+        }
+    }
+}
+impl Eq for MouseMotionNormalization {}
+impl std::hash::Hash for MouseMotionNormalization {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            MouseMotionNormalization::Raw => {}
+            MouseMotionNormalization::Rate { scale }
+            | MouseMotionNormalization::Stick { scale } => {
+                FloatOrd(scale).hash(state);
+            }
+        }
+    }
+}
+
 impl From<GamepadAxisType> for AxisType {
     fn from(axis_type: GamepadAxisType) -> Self {
         AxisType::Gamepad(axis_type)
@@ -513,6 +630,9 @@ impl DualAxisData {
     ///
     /// This method can result in values with a greater maximum magnitude than expected!
     /// Use [`AxisPair::clamp_length`] to limit the resulting direction.
+    ///
+    /// This is how the axis pairs of several bindings are combined by [`AggregationPolicy::Sum`](crate::user_input::AggregationPolicy::Sum),
+    /// the default for each action.
     pub fn merged_with(&self, other: DualAxisData) -> DualAxisData {
         DualAxisData::from_xy(self.xy() + other.xy())
     }
@@ -3,13 +3,13 @@
 use crate::action_state::ActionData;
 use crate::axislike::{VirtualAxis, VirtualDPad};
 use crate::input_map::InputMap;
+use crate::input_set::InputSet;
 use crate::input_streams::InputStreams;
 use crate::user_input::{InputKind, UserInput};
 use crate::Actionlike;
 
 use bevy::prelude::Resource;
 use itertools::Itertools;
-use petitset::PetitSet;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::marker::PhantomData;
@@ -208,7 +208,7 @@ impl<A: Actionlike> Clash<A> {
 
 // Does the `button` clash with the `chord`?
 #[must_use]
-fn button_chord_clash(button: &InputKind, chord: &PetitSet<InputKind, 8>) -> bool {
+fn button_chord_clash(button: &InputKind, chord: &InputSet<InputKind, 8>) -> bool {
     if chord.len() <= 1 {
         return false;
     }
@@ -218,7 +218,7 @@ fn button_chord_clash(button: &InputKind, chord: &PetitSet<InputKind, 8>) -> boo
 
 // Does the `dpad` clash with the `chord`?
 #[must_use]
-fn dpad_chord_clash(dpad: &VirtualDPad, chord: &PetitSet<InputKind, 8>) -> bool {
+fn dpad_chord_clash(dpad: &VirtualDPad, chord: &InputSet<InputKind, 8>) -> bool {
     if chord.len() <= 1 {
         return false;
     }
@@ -271,7 +271,7 @@ fn virtual_axis_dpad_clash(axis: &VirtualAxis, dpad: &VirtualDPad) -> bool {
 }
 
 #[must_use]
-fn virtual_axis_chord_clash(axis: &VirtualAxis, chord: &PetitSet<InputKind, 8>) -> bool {
+fn virtual_axis_chord_clash(axis: &VirtualAxis, chord: &InputSet<InputKind, 8>) -> bool {
     if chord.len() <= 1 {
         return false;
     }
@@ -289,7 +289,7 @@ fn virtual_axis_virtual_axis_clash(axis1: &VirtualAxis, axis2: &VirtualAxis) ->
 
 /// Does the `chord_a` clash with `chord_b`?
 #[must_use]
-fn chord_chord_clash(chord_a: &PetitSet<InputKind, 8>, chord_b: &PetitSet<InputKind, 8>) -> bool {
+fn chord_chord_clash(chord_a: &InputSet<InputKind, 8>, chord_b: &InputSet<InputKind, 8>) -> bool {
     if chord_a.len() <= 1 || chord_b.len() <= 1 {
         return false;
     }
@@ -719,5 +719,50 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn clashes_between_nested_actions() {
+            #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+            enum MovementAction {
+                Jump,
+            }
+
+            #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+            enum CombatAction {
+                JumpAttack,
+            }
+
+            #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+            enum GameAction {
+                #[actionlike(nested)]
+                Movement(MovementAction),
+                #[actionlike(nested)]
+                Combat(CombatAction),
+            }
+
+            let mut app = App::new();
+            app.add_plugin(InputPlugin);
+
+            let mut input_map = InputMap::default();
+            input_map.insert(Space, GameAction::Movement(MovementAction::Jump));
+            input_map.insert_chord(
+                [LControl, Space],
+                GameAction::Combat(CombatAction::JumpAttack),
+            );
+
+            app.send_input(Space);
+            app.send_input(LControl);
+            app.update();
+
+            let action_data = input_map.which_pressed(
+                &InputStreams::from_world(&app.world, None),
+                ClashStrategy::PrioritizeLongest,
+            );
+
+            let jump = GameAction::Movement(MovementAction::Jump).index();
+            let jump_attack = GameAction::Combat(CombatAction::JumpAttack).index();
+            assert!(action_data[jump].state.released());
+            assert!(action_data[jump_attack].state.pressed());
+        }
     }
 }
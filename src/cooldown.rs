@@ -0,0 +1,736 @@
+//! Cooldowns and charges that limit how often actions can be used
+//!
+//! Most action games gate abilities behind cooldowns: once `Dash` is used, it cannot be used again for a few seconds.
+//! Add a [`Cooldowns`] component next to an entity's [`ActionState`](crate::action_state::ActionState),
+//! or insert it as a resource, and check [`Cooldowns::trigger_if_ready`] before acting on a press.
+//!
+//! Cooldowns are advanced by [`tick_action_state`](crate::systems::tick_action_state),
+//! so they follow the same clock as the durations of the [`ActionState`](crate::action_state::ActionState).
+//! Like the [`ActionState`](crate::action_state::ActionState), [`Cooldowns`] are serializable,
+//! so they can be stored in save games.
+//! They are stored by action rather than by [`Actionlike::index`],
+//! so saves can still be loaded after actions are added or reordered.
+//!
+//! # Example
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::cooldown::{Cooldown, Cooldowns};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+//! enum Ability {
+//!     Dash,
+//!     Fireball,
+//! }
+//!
+//! fn use_abilities(mut query: Query<(&ActionState<Ability>, &mut Cooldowns<Ability>)>) {
+//!     for (action_state, mut cooldowns) in query.iter_mut() {
+//!         for ability in action_state.get_just_pressed() {
+//!             if cooldowns.trigger_if_ready(ability).is_ok() {
+//!                 info!("Used {ability:?}");
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!     .add_plugin(InputManagerPlugin::<Ability>::default())
+//!     .add_system(use_abilities);
+//!
+//! app.world.spawn((
+//!     InputManagerBundle::<Ability>::default(),
+//!     Cooldowns::new([
+//!         (Cooldown::from_secs(1.0), Ability::Dash),
+//!         (Cooldown::from_secs(5.0), Ability::Fireball),
+//!     ]),
+//! ));
+//! ```
+
+use crate::errors::CannotUseAction;
+use crate::Actionlike;
+
+use bevy::ecs::{component::Component, system::Resource};
+use bevy::reflect::{FromReflect, Reflect};
+use bevy::utils::Duration;
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// The [`Cooldown`] and [`Charges`] of each action of the type `A`
+///
+/// Actions without a cooldown or charges are always ready,
+/// and are not affected by the [global cooldown](Cooldowns::set_global_cooldown).
+///
+/// See the [module-level documentation](crate::cooldown) for more information.
+#[derive(
+    Resource, Component, Clone, Debug, PartialEq, Serialize, Deserialize, Reflect, FromReflect,
+)]
+#[serde(bound(serialize = "A: Serialize", deserialize = "A: Deserialize<'de>"))]
+pub struct Cooldowns<A: Actionlike> {
+    /// The [`Cooldown`] of each action
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(
+        serialize_with = "serialize_by_action::<A, _, _>",
+        deserialize_with = "deserialize_by_action::<A, _, _>"
+    )]
+    cooldowns: Vec<Option<Cooldown>>,
+    /// The [`Charges`] of each action
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(
+        serialize_with = "serialize_by_action::<A, _, _>",
+        deserialize_with = "deserialize_by_action::<A, _, _>"
+    )]
+    charges: Vec<Option<Charges>>,
+    /// A cooldown shared by every action that has a cooldown or charges
+    global_cooldown: Option<Cooldown>,
+    #[reflect(ignore)]
+    #[serde(skip)]
+    _phantom: PhantomData<A>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Actionlike> Default for Cooldowns<A> {
+    fn default() -> Self {
+        Self {
+            cooldowns: vec![None; A::N_VARIANTS],
+            charges: vec![None; A::N_VARIANTS],
+            global_cooldown: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> Cooldowns<A> {
+    /// Creates a new [`Cooldowns`] from an iterator of `(cooldown, action)` pairs
+    ///
+    /// If an action is listed more than once, the last cooldown is used.
+    #[must_use]
+    pub fn new(cooldowns: impl IntoIterator<Item = (Cooldown, A)>) -> Self {
+        let mut new = Self::default();
+        for (cooldown, action) in cooldowns {
+            new.set(action, cooldown);
+        }
+        new
+    }
+
+    /// Sets the [`Cooldown`] of the `action`, replacing any previous cooldown
+    pub fn set(&mut self, action: A, cooldown: Cooldown) -> &mut Self {
+        self.cooldowns[action.index()] = Some(cooldown);
+        self
+    }
+
+    /// Removes the [`Cooldown`] of the `action`, returning it if it existed
+    pub fn remove(&mut self, action: A) -> Option<Cooldown> {
+        self.cooldowns[action.index()].take()
+    }
+
+    /// The [`Cooldown`] of the `action`, if any
+    #[must_use]
+    pub fn get(&self, action: A) -> Option<&Cooldown> {
+        self.cooldowns[action.index()].as_ref()
+    }
+
+    /// A mutable reference to the [`Cooldown`] of the `action`, if any
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> Option<&mut Cooldown> {
+        self.cooldowns[action.index()].as_mut()
+    }
+
+    /// Sets the [`Charges`] of the `action`, or removes them if `charges` is [`None`]
+    ///
+    /// Actions with charges can be used while they have any charges left, spending one charge each time.
+    /// Each time the [`Cooldown`] of the action elapses, one charge is replenished,
+    /// and the cooldown restarts until the action is fully charged.
+    /// Actions with charges but no cooldown are never replenished, until [`Charges::refill`] is called.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::utils::Duration;
+    /// use leafwing_input_manager::cooldown::{Charges, Cooldown, Cooldowns};
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Ability {
+    ///     Blink,
+    /// }
+    ///
+    /// let mut cooldowns = Cooldowns::new([(Cooldown::from_secs(3.0), Ability::Blink)]);
+    /// cooldowns.set_charges(Ability::Blink, Some(Charges::new(2)));
+    ///
+    /// // Blink can be used twice in quick succession...
+    /// assert!(cooldowns.trigger_if_ready(Ability::Blink).is_ok());
+    /// assert!(cooldowns.trigger_if_ready(Ability::Blink).is_ok());
+    /// assert!(cooldowns.trigger_if_ready(Ability::Blink).is_err());
+    ///
+    /// // ...and regains a charge each time its cooldown elapses
+    /// cooldowns.tick(Duration::from_secs(3));
+    /// assert_eq!(cooldowns.charges(Ability::Blink).unwrap().current(), 1);
+    /// cooldowns.tick(Duration::from_secs(3));
+    /// assert_eq!(cooldowns.charges(Ability::Blink).unwrap().current(), 2);
+    /// ```
+    pub fn set_charges(&mut self, action: A, charges: Option<Charges>) -> &mut Self {
+        self.charges[action.index()] = charges;
+        self
+    }
+
+    /// The [`Charges`] of the `action`, if any
+    ///
+    /// See [`Cooldowns::set_charges`] for more details.
+    #[must_use]
+    pub fn charges(&self, action: A) -> Option<&Charges> {
+        self.charges[action.index()].as_ref()
+    }
+
+    /// A mutable reference to the [`Charges`] of the `action`, if any
+    #[must_use]
+    pub fn charges_mut(&mut self, action: A) -> Option<&mut Charges> {
+        self.charges[action.index()].as_mut()
+    }
+
+    /// Sets the cooldown shared by every action that has a [`Cooldown`] or [`Charges`],
+    /// or removes it if `global_cooldown` is [`None`]
+    ///
+    /// Triggering any of those actions starts the global cooldown,
+    /// and none of them are ready until it has elapsed.
+    /// This prevents players from using several abilities in the same instant.
+    pub fn set_global_cooldown(&mut self, global_cooldown: Option<Cooldown>) -> &mut Self {
+        self.global_cooldown = global_cooldown;
+        self
+    }
+
+    /// The cooldown shared by every action that has a [`Cooldown`] or [`Charges`], if any
+    #[must_use]
+    pub fn global_cooldown(&self) -> Option<&Cooldown> {
+        self.global_cooldown.as_ref()
+    }
+
+    /// Does the action at `index` have a [`Cooldown`] or [`Charges`]?
+    #[must_use]
+    fn is_limited(&self, index: usize) -> bool {
+        self.cooldowns[index].is_some() || self.charges[index].is_some()
+    }
+
+    /// Checks whether the `action` can be used
+    ///
+    /// Returns the reason why the action cannot be used, if it is not ready.
+    pub fn check(&self, action: A) -> Result<(), CannotUseAction> {
+        let index = action.index();
+        if !self.is_limited(index) {
+            return Ok(());
+        }
+
+        if let Some(global_cooldown) = &self.global_cooldown {
+            if !global_cooldown.ready() {
+                return Err(CannotUseAction::OnGlobalCooldown);
+            }
+        }
+
+        let ready = match (&self.charges[index], &self.cooldowns[index]) {
+            (Some(charges), _) => charges.current() > 0,
+            (None, Some(cooldown)) => cooldown.ready(),
+            (None, None) => true,
+        };
+
+        if ready {
+            Ok(())
+        } else {
+            Err(CannotUseAction::OnCooldown)
+        }
+    }
+
+    /// Can the `action` be used?
+    ///
+    /// See [`Cooldowns::check`] to find out why an action is not ready.
+    #[must_use]
+    pub fn ready(&self, action: A) -> bool {
+        self.check(action).is_ok()
+    }
+
+    /// Uses the `action`, spending one of its [`Charges`] and starting its [`Cooldown`] and the global cooldown
+    ///
+    /// This does not check whether the action is ready: use [`Cooldowns::trigger_if_ready`] to do so.
+    pub fn trigger(&mut self, action: A) {
+        let index = action.index();
+        if !self.is_limited(index) {
+            return;
+        }
+
+        if let Some(global_cooldown) = &mut self.global_cooldown {
+            global_cooldown.trigger();
+        }
+
+        match (&mut self.charges[index], &mut self.cooldowns[index]) {
+            (Some(charges), cooldown) => {
+                // The cooldown is already running while charges are being replenished
+                if charges.current == charges.max {
+                    if let Some(cooldown) = cooldown {
+                        cooldown.trigger();
+                    }
+                }
+                charges.current = charges.current.saturating_sub(1);
+            }
+            (None, Some(cooldown)) => cooldown.trigger(),
+            (None, None) => (),
+        }
+    }
+
+    /// Uses the `action` if it is [ready](Cooldowns::ready), as described in [`Cooldowns::trigger`]
+    ///
+    /// Returns the reason why the action cannot be used if it is not ready, in which case nothing is changed.
+    pub fn trigger_if_ready(&mut self, action: A) -> Result<(), CannotUseAction> {
+        self.check(action.clone())?;
+        self.trigger(action);
+        Ok(())
+    }
+
+    /// Makes every action ready immediately, refilling their [`Charges`]
+    pub fn refresh_all(&mut self) {
+        if let Some(global_cooldown) = &mut self.global_cooldown {
+            global_cooldown.refresh();
+        }
+
+        for cooldown in self.cooldowns.iter_mut().flatten() {
+            cooldown.refresh();
+        }
+
+        for charges in self.charges.iter_mut().flatten() {
+            charges.refill();
+        }
+    }
+
+    /// Advances every cooldown by `delta`, replenishing [`Charges`] as their cooldowns elapse
+    ///
+    /// This is called by [`tick_action_state`](crate::systems::tick_action_state).
+    pub fn tick(&mut self, delta: Duration) {
+        if let Some(global_cooldown) = &mut self.global_cooldown {
+            global_cooldown.tick(delta, None);
+        }
+
+        for (cooldown, charges) in self.cooldowns.iter_mut().zip(self.charges.iter_mut()) {
+            if let Some(cooldown) = cooldown {
+                cooldown.tick(delta, charges.as_mut());
+            }
+        }
+    }
+}
+
+/// Serializes the `values` of each action, indexed by [`Actionlike::index`], as a map from each action to its value
+///
+/// Actions without a value are skipped.
+fn serialize_by_action<A, T, S>(values: &[Option<T>], serializer: S) -> Result<S::Ok, S::Error>
+where
+    A: Actionlike + Serialize,
+    T: Serialize,
+    S: Serializer,
+{
+    serializer.collect_map(
+        A::variants()
+            .zip(values)
+            .filter_map(|(action, value)| Some((action, value.as_ref()?))),
+    )
+}
+
+/// Deserializes a map from actions to values, as created by [`serialize_by_action`], indexing them by [`Actionlike::index`]
+///
+/// Actions that are missing from the map have no value.
+fn deserialize_by_action<'de, A, T, D>(deserializer: D) -> Result<Vec<Option<T>>, D::Error>
+where
+    A: Actionlike + Deserialize<'de>,
+    T: Clone + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    struct ByActionVisitor<A, T> {
+        _phantom: PhantomData<(A, T)>,
+    }
+
+    impl<'de, A, T> Visitor<'de> for ByActionVisitor<A, T>
+    where
+        A: Actionlike + Deserialize<'de>,
+        T: Clone + Deserialize<'de>,
+    {
+        type Value = Vec<Option<T>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map from actions to their values")
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+            let mut values = vec![None; A::N_VARIANTS];
+            while let Some((action, value)) = map.next_entry::<A, T>()? {
+                values[action.index()] = Some(value);
+            }
+
+            Ok(values)
+        }
+    }
+
+    deserializer.deserialize_map(ByActionVisitor::<A, T> {
+        _phantom: PhantomData,
+    })
+}
+
+/// A timer that must elapse before an action can be used again
+///
+/// New cooldowns are ready immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct Cooldown {
+    max_time: Duration,
+    elapsed_time: Duration,
+}
+
+impl Cooldown {
+    /// Creates a cooldown that takes `max_time` to elapse
+    #[must_use]
+    pub fn new(max_time: Duration) -> Self {
+        Self {
+            max_time,
+            elapsed_time: max_time,
+        }
+    }
+
+    /// Creates a cooldown that takes `max_time` seconds to elapse
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_time` is negative, infinite or not a number.
+    #[must_use]
+    pub fn from_secs(max_time: f32) -> Self {
+        Self::new(Duration::from_secs_f32(max_time))
+    }
+
+    /// Has this cooldown elapsed?
+    #[must_use]
+    pub fn ready(&self) -> bool {
+        self.elapsed_time >= self.max_time
+    }
+
+    /// Restarts this cooldown
+    pub fn trigger(&mut self) {
+        self.elapsed_time = Duration::ZERO;
+    }
+
+    /// Makes this cooldown elapse immediately
+    pub fn refresh(&mut self) {
+        self.elapsed_time = self.max_time;
+    }
+
+    /// Advances this cooldown by `delta`
+    ///
+    /// If `charges` are provided, one charge is replenished each time the cooldown elapses,
+    /// and the cooldown restarts until they are full.
+    pub fn tick(&mut self, delta: Duration, charges: Option<&mut Charges>) {
+        let charges = match charges {
+            Some(charges) => charges,
+            None => {
+                self.elapsed_time = (self.elapsed_time + delta).min(self.max_time);
+                return;
+            }
+        };
+
+        let mut delta = delta;
+        while charges.current < charges.max {
+            let remaining = self.remaining();
+            if delta < remaining {
+                self.elapsed_time += delta;
+                return;
+            }
+
+            delta -= remaining;
+            charges.current += 1;
+            self.elapsed_time = Duration::ZERO;
+        }
+
+        // Fully charged actions have nothing left to wait for
+        self.refresh();
+    }
+
+    /// How long does this cooldown take to elapse?
+    #[must_use]
+    pub fn max_time(&self) -> Duration {
+        self.max_time
+    }
+
+    /// Sets how long this cooldown takes to elapse, keeping the time that has already elapsed
+    pub fn set_max_time(&mut self, max_time: Duration) {
+        self.max_time = max_time;
+        self.elapsed_time = self.elapsed_time.min(max_time);
+    }
+
+    /// How long has this cooldown been running for?
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_time
+    }
+
+    /// How long is left until this cooldown elapses?
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.max_time.saturating_sub(self.elapsed_time)
+    }
+
+    /// How far has this cooldown elapsed, from `0.0` to `1.0`?
+    ///
+    /// This is useful for drawing cooldown indicators.
+    #[must_use]
+    pub fn fraction_elapsed(&self) -> f32 {
+        if self.max_time.is_zero() {
+            1.0
+        } else {
+            self.elapsed_time.as_secs_f32() / self.max_time.as_secs_f32()
+        }
+    }
+}
+
+/// The number of times an action can be used before waiting for its [`Cooldown`]
+///
+/// See [`Cooldowns::set_charges`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, FromReflect)]
+pub struct Charges {
+    current: u8,
+    max: u8,
+}
+
+impl Charges {
+    /// Creates `max` charges, which start out full
+    #[must_use]
+    pub fn new(max: u8) -> Self {
+        Self { current: max, max }
+    }
+
+    /// How many charges are left?
+    #[must_use]
+    pub fn current(&self) -> u8 {
+        self.current
+    }
+
+    /// How many charges can be stored?
+    #[must_use]
+    pub fn max(&self) -> u8 {
+        self.max
+    }
+
+    /// Sets how many charges are left, up to the maximum
+    pub fn set_current(&mut self, current: u8) {
+        self.current = current.min(self.max);
+    }
+
+    /// Replenishes every charge
+    pub fn refill(&mut self) {
+        self.current = self.max;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    enum Ability {
+        Dash,
+        Fireball,
+        Walk,
+    }
+
+    #[test]
+    fn cooldown_lifecycle() {
+        let mut cooldowns = Cooldowns::new([(Cooldown::from_secs(1.0), Ability::Dash)]);
+        assert!(cooldowns.ready(Ability::Dash));
+
+        assert_eq!(cooldowns.trigger_if_ready(Ability::Dash), Ok(()));
+        assert_eq!(
+            cooldowns.trigger_if_ready(Ability::Dash),
+            Err(CannotUseAction::OnCooldown)
+        );
+
+        cooldowns.tick(Duration::from_millis(750));
+        assert!(!cooldowns.ready(Ability::Dash));
+        assert_eq!(
+            cooldowns.get(Ability::Dash).unwrap().remaining(),
+            Duration::from_millis(250)
+        );
+
+        cooldowns.tick(Duration::from_millis(250));
+        assert!(cooldowns.ready(Ability::Dash));
+
+        // Actions without a cooldown are always ready
+        cooldowns.trigger(Ability::Walk);
+        assert!(cooldowns.ready(Ability::Walk));
+    }
+
+    #[test]
+    fn global_cooldown() {
+        let mut cooldowns = Cooldowns::new([
+            (Cooldown::from_secs(1.0), Ability::Dash),
+            (Cooldown::from_secs(5.0), Ability::Fireball),
+        ]);
+        cooldowns.set_global_cooldown(Some(Cooldown::from_secs(0.5)));
+
+        assert_eq!(cooldowns.trigger_if_ready(Ability::Dash), Ok(()));
+        assert_eq!(
+            cooldowns.check(Ability::Fireball),
+            Err(CannotUseAction::OnGlobalCooldown)
+        );
+        // Actions without a cooldown ignore the global cooldown
+        assert!(cooldowns.ready(Ability::Walk));
+
+        cooldowns.tick(Duration::from_millis(500));
+        assert_eq!(cooldowns.trigger_if_ready(Ability::Fireball), Ok(()));
+
+        cooldowns.refresh_all();
+        assert!(cooldowns.ready(Ability::Dash));
+        assert!(cooldowns.ready(Ability::Fireball));
+    }
+
+    #[test]
+    fn charges_replenish_one_at_a_time() {
+        let mut cooldowns = Cooldowns::new([(Cooldown::from_secs(1.0), Ability::Dash)]);
+        cooldowns.set_charges(Ability::Dash, Some(Charges::new(3)));
+
+        for _ in 0..3 {
+            cooldowns.trigger(Ability::Dash);
+        }
+        assert!(!cooldowns.ready(Ability::Dash));
+
+        // Time left over from one charge counts towards the next
+        cooldowns.tick(Duration::from_millis(1500));
+        assert_eq!(cooldowns.charges(Ability::Dash).unwrap().current(), 1);
+        assert_eq!(
+            cooldowns.get(Ability::Dash).unwrap().elapsed(),
+            Duration::from_millis(500)
+        );
+
+        // Using a charge does not restart the cooldown of the next one
+        cooldowns.trigger(Ability::Dash);
+        cooldowns.tick(Duration::from_millis(500));
+        assert_eq!(cooldowns.charges(Ability::Dash).unwrap().current(), 1);
+
+        cooldowns.tick(Duration::from_secs(10));
+        assert_eq!(cooldowns.charges(Ability::Dash).unwrap().current(), 3);
+        assert!(cooldowns.get(Ability::Dash).unwrap().ready());
+    }
+
+    #[test]
+    fn charges_without_cooldown() {
+        let mut cooldowns = Cooldowns::<Ability>::default();
+        cooldowns.set_charges(Ability::Fireball, Some(Charges::new(1)));
+
+        assert_eq!(cooldowns.trigger_if_ready(Ability::Fireball), Ok(()));
+        cooldowns.tick(Duration::from_secs(60));
+        assert!(!cooldowns.ready(Ability::Fireball));
+
+        cooldowns.charges_mut(Ability::Fireball).unwrap().refill();
+        assert!(cooldowns.ready(Ability::Fireball));
+    }
+
+    #[test]
+    fn serde() {
+        use serde_test::{assert_tokens, Token};
+
+        let mut cooldown = Cooldown::from_secs(2.0);
+        cooldown.tick(Duration::from_millis(500), None);
+        cooldown.trigger();
+        cooldown.tick(Duration::from_millis(1500), None);
+
+        assert_tokens(
+            &cooldown,
+            &[
+                Token::Struct {
+                    name: "Cooldown",
+                    len: 2,
+                },
+                Token::Str("max_time"),
+                Token::Struct {
+                    name: "Duration",
+                    len: 2,
+                },
+                Token::Str("secs"),
+                Token::U64(2),
+                Token::Str("nanos"),
+                Token::U32(0),
+                Token::StructEnd,
+                Token::Str("elapsed_time"),
+                Token::Struct {
+                    name: "Duration",
+                    len: 2,
+                },
+                Token::Str("secs"),
+                Token::U64(1),
+                Token::Str("nanos"),
+                Token::U32(500_000_000),
+                Token::StructEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn serde_by_action() {
+        use serde_test::{assert_tokens, Token};
+
+        let mut cooldowns = Cooldowns::<Ability>::default();
+        cooldowns.set_charges(Ability::Fireball, Some(Charges::new(2)));
+
+        assert_tokens(
+            &cooldowns,
+            &[
+                Token::Struct {
+                    name: "Cooldowns",
+                    len: 3,
+                },
+                Token::Str("cooldowns"),
+                Token::Map { len: None },
+                Token::MapEnd,
+                Token::Str("charges"),
+                Token::Map { len: None },
+                Token::UnitVariant {
+                    name: "Ability",
+                    variant: "Fireball",
+                },
+                Token::Struct {
+                    name: "Charges",
+                    len: 2,
+                },
+                Token::Str("current"),
+                Token::U8(2),
+                Token::Str("max"),
+                Token::U8(2),
+                Token::StructEnd,
+                Token::MapEnd,
+                Token::Str("global_cooldown"),
+                Token::None,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn load_old_save() {
+        // An older version of `Ability`, with fewer actions in a different order
+        #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+        enum OldAbility {
+            Fireball,
+            Dash,
+        }
+
+        let mut old_cooldowns = Cooldowns::new([(Cooldown::from_secs(5.0), OldAbility::Fireball)]);
+        old_cooldowns.trigger(OldAbility::Fireball);
+        let save = ron::to_string(&old_cooldowns).unwrap();
+
+        let mut cooldowns: Cooldowns<Ability> = ron::from_str(&save).unwrap();
+        assert!(!cooldowns.ready(Ability::Fireball));
+        assert!(cooldowns.get(Ability::Dash).is_none());
+
+        // Actions that were added since the save can be used and given cooldowns
+        assert!(cooldowns.ready(Ability::Walk));
+        cooldowns.set(Ability::Walk, Cooldown::from_secs(1.0));
+        cooldowns.trigger(Ability::Walk);
+        cooldowns.tick(Duration::from_secs(5));
+        assert!(cooldowns.ready(Ability::Fireball));
+        assert!(cooldowns.ready(Ability::Walk));
+    }
+}
@@ -0,0 +1,338 @@
+//! Actions that are defined at runtime, such as those added by mods
+//!
+//! [`Actionlike`] is usually derived on an enum, which fixes the set of actions at compile time.
+//! [`DynamicAction`] implements [`Actionlike`] using a registry of named actions instead,
+//! so new actions can be registered while your game is running
+//! and used with the same [`InputMap`](crate::input_map::InputMap), [`ActionState`](crate::action_state::ActionState),
+//! plugin and serialization code as any other action type.
+//!
+//! Actions are identified by their name, such as `"mod_x/cast_spell"`, which is also used when serializing them.
+//! Registering a name that is already registered returns the existing action,
+//! so it is safe for each mod to register the actions it needs every time it is loaded.
+//!
+//! The registry is global, rather than a [`Resource`](bevy::ecs::system::Resource),
+//! as the methods of [`Actionlike`] and deserialization have no access to the [`World`](bevy::ecs::world::World):
+//! a [`DynamicAction`] is a plain index, and must find its name and metadata without one.
+//! As a result, the registry is shared by every [`App`](bevy::app::App) in the process, and cannot be reset.
+//! Actions registered by one app or test can be seen by all of the others,
+//! so give your actions distinct names, such as by prefixing them with the name of your mod.
+//! Registered actions cannot be removed, and at most [`DynamicAction::CAPACITY`] actions can be registered,
+//! which also bounds the memory used to store their names and metadata.
+//! Registration takes a lock, but looking up the metadata of an action does not,
+//! so dynamic actions are as cheap to use each frame as any other action type.
+//!
+//! # Example
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::dynamic_action::{DynamicAction, DynamicActionDescriptor};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! let cast_spell = DynamicAction::register_with(DynamicActionDescriptor {
+//!     category: Some("Magic".to_string()),
+//!     kind: Some(ActionKind::Button),
+//!     ..DynamicActionDescriptor::new("mod_x/cast_spell")
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(DynamicAction::from_name("mod_x/cast_spell"), Some(cast_spell));
+//! assert_eq!(cast_spell.metadata().category, Some("Magic"));
+//!
+//! let mut input_map = InputMap::<DynamicAction>::default();
+//! input_map.insert(KeyCode::Q, cast_spell);
+//! ```
+
+use crate::errors::TooManyDynamicActions;
+use crate::versioning::VersionedActionlike;
+use crate::{ActionKind, ActionMetadata, Actionlike};
+
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// The actions that have been registered, indexed by [`DynamicAction::index`]
+///
+/// Each slot is written once, when its action is registered, and can then be read without locking.
+static REGISTRY: [OnceLock<RegisteredAction>; DynamicAction::CAPACITY] =
+    [const { OnceLock::new() }; DynamicAction::CAPACITY];
+
+/// The number of actions that have been registered
+///
+/// This is only increased once the slot of the new action has been written.
+static N_REGISTERED: AtomicUsize = AtomicUsize::new(0);
+
+/// Held while registering an action, so that each name is only registered once
+static REGISTRATION: Mutex<()> = Mutex::new(());
+
+/// The information stored for each registered action
+///
+/// Strings are leaked on registration, so that they can be returned in an [`ActionMetadata`].
+/// This is bounded by [`DynamicAction::CAPACITY`], as actions are never removed.
+#[derive(Debug)]
+struct RegisteredAction {
+    metadata: ActionMetadata,
+    kind: Option<ActionKind>,
+}
+
+/// An [`Actionlike`] action that was registered at runtime
+///
+/// See the [module-level documentation](crate::dynamic_action) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DynamicAction {
+    id: u16,
+}
+
+impl DynamicAction {
+    /// The maximum number of actions that can be registered
+    ///
+    /// Every [`ActionState<DynamicAction>`](crate::action_state::ActionState) and [`InputMap<DynamicAction>`](crate::input_map::InputMap)
+    /// reserves space for this many actions, so that actions registered after they are created can still be used.
+    pub const CAPACITY: usize = 256;
+
+    /// Registers an action with the provided `name`, using the default metadata
+    ///
+    /// If an action with this name was already registered, it is returned instead.
+    pub fn register(name: &str) -> Result<DynamicAction, TooManyDynamicActions> {
+        Self::register_with(DynamicActionDescriptor::new(name))
+    }
+
+    /// Registers an action with the provided `descriptor`
+    ///
+    /// If an action with the same name was already registered, it is returned unchanged instead.
+    pub fn register_with(
+        descriptor: DynamicActionDescriptor,
+    ) -> Result<DynamicAction, TooManyDynamicActions> {
+        // A panic while registering cannot leave the registry in an inconsistent state
+        let _registration = REGISTRATION
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(action) = Self::from_name(&descriptor.name) {
+            return Ok(action);
+        }
+
+        let id = N_REGISTERED.load(Ordering::Acquire);
+        if id >= Self::CAPACITY {
+            return Err(TooManyDynamicActions {
+                capacity: Self::CAPACITY,
+            });
+        }
+
+        let leak = |string: String| -> &'static str { Box::leak(string.into_boxed_str()) };
+        REGISTRY[id]
+            .set(RegisteredAction {
+                metadata: ActionMetadata {
+                    name: leak(descriptor.name),
+                    category: descriptor.category.map(leak),
+                    description: descriptor.description.map(leak),
+                    rebindable: descriptor.rebindable,
+                },
+                kind: descriptor.kind,
+            })
+            .expect("slots are only written while registering, one action at a time");
+        N_REGISTERED.store(id + 1, Ordering::Release);
+
+        Ok(DynamicAction { id: id as u16 })
+    }
+
+    /// Returns the registered action with the provided `name`, if any
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<DynamicAction> {
+        (0..Self::n_registered())
+            .map(|id| DynamicAction { id: id as u16 })
+            .find(|action| action.name() == name)
+    }
+
+    /// The unique name of this action
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.metadata().name
+    }
+
+    /// The number of actions that have been registered so far
+    #[must_use]
+    pub fn n_registered() -> usize {
+        N_REGISTERED.load(Ordering::Acquire)
+    }
+
+    /// The information stored when this action was registered
+    fn registered(&self) -> &'static RegisteredAction {
+        // Actions are only created once their slot has been written
+        REGISTRY[self.index()]
+            .get()
+            .expect("dynamic actions are only created once registered")
+    }
+}
+
+impl Actionlike for DynamicAction {
+    const N_VARIANTS: usize = Self::CAPACITY;
+
+    fn get_at(index: usize) -> Option<Self> {
+        (index < Self::n_registered()).then_some(DynamicAction { id: index as u16 })
+    }
+
+    fn index(&self) -> usize {
+        self.id as usize
+    }
+
+    fn metadata(&self) -> ActionMetadata {
+        self.registered().metadata
+    }
+
+    fn kind(&self) -> Option<ActionKind> {
+        self.registered().kind
+    }
+}
+
+// Actions of mods that are no longer installed are skipped when loading a `VersionedInputMap`
+impl VersionedActionlike for DynamicAction {
+    const VERSION: u32 = 0;
+}
+
+impl Display for DynamicAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Serialize for DynamicAction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for DynamicAction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct DynamicActionVisitor;
+
+        impl<'de> Visitor<'de> for DynamicActionVisitor {
+            type Value = DynamicAction;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "the name of a registered `DynamicAction`")
+            }
+
+            fn visit_str<E: Error>(self, name: &str) -> Result<Self::Value, E> {
+                DynamicAction::from_name(name)
+                    .ok_or_else(|| E::custom(format!("unknown dynamic action `{name}`")))
+            }
+        }
+
+        deserializer.deserialize_str(DynamicActionVisitor)
+    }
+}
+
+/// Describes a [`DynamicAction`] to be registered with [`DynamicAction::register_with`]
+///
+/// The fields other than `name` correspond to the fields of [`ActionMetadata`] and [`Actionlike::kind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicActionDescriptor {
+    /// The unique name of the action, which is also displayed to players
+    pub name: String,
+    /// The group of related actions that this action belongs to
+    pub category: Option<String>,
+    /// A longer explanation of what this action does, suitable for a tooltip
+    pub description: Option<String>,
+    /// Can players change the bindings of this action?
+    pub rebindable: bool,
+    /// The kind of data that this action represents, if declared
+    pub kind: Option<ActionKind>,
+}
+
+impl DynamicActionDescriptor {
+    /// Creates a descriptor for a rebindable action with the provided `name` and no other metadata
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        DynamicActionDescriptor {
+            name: name.into(),
+            category: None,
+            description: None,
+            rebindable: true,
+            kind: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_map::InputMap;
+    use bevy::input::keyboard::KeyCode;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    // The registry is shared between tests, so each test uses its own names
+
+    #[test]
+    fn registration() {
+        let fireball = DynamicAction::register("registration/fireball").unwrap();
+        let heal = DynamicAction::register_with(DynamicActionDescriptor {
+            description: Some("Restore some health".to_string()),
+            kind: Some(ActionKind::Button),
+            ..DynamicActionDescriptor::new("registration/heal")
+        })
+        .unwrap();
+
+        assert_ne!(fireball, heal);
+        assert_eq!(
+            DynamicAction::register("registration/fireball"),
+            Ok(fireball)
+        );
+        assert_eq!(DynamicAction::from_name("registration/heal"), Some(heal));
+        assert_eq!(DynamicAction::from_name("registration/missing"), None);
+
+        assert_eq!(heal.name(), "registration/heal");
+        assert_eq!(heal.metadata().description, Some("Restore some health"));
+        assert_eq!(heal.kind(), Some(ActionKind::Button));
+        assert_eq!(fireball.kind(), None);
+
+        assert!(DynamicAction::variants().any(|action| action == heal));
+        assert_eq!(DynamicAction::get_at(heal.index()), Some(heal));
+    }
+
+    #[test]
+    fn variants_len() {
+        DynamicAction::register("variants_len/block").unwrap();
+
+        // Other tests may register actions concurrently, so the count can only grow between reads
+        let registered = DynamicAction::n_registered();
+        let mut variants = DynamicAction::variants();
+        let len = variants.len();
+        assert!(registered <= len && len < DynamicAction::CAPACITY);
+
+        variants.next();
+        assert_eq!(variants.len(), len - 1);
+
+        // Actions registered after the iterator was created are not returned
+        DynamicAction::register("variants_len/parry").unwrap();
+        assert_eq!(variants.count(), len - 1);
+    }
+
+    #[test]
+    fn registered_after_input_map() {
+        let mut input_map = InputMap::<DynamicAction>::default();
+        let dash = DynamicAction::register("late/dash").unwrap();
+
+        input_map.insert(KeyCode::LShift, dash);
+        assert_eq!(input_map.get(dash).len(), 1);
+        assert!(input_map
+            .iter()
+            .any(|(inputs, action)| action == dash && !inputs.is_empty()));
+    }
+
+    #[test]
+    fn serialization() {
+        let jump = DynamicAction::register("serialization/jump").unwrap();
+        assert_tokens(&jump, &[Token::Str("serialization/jump")]);
+
+        assert_de_tokens_error::<DynamicAction>(
+            &[Token::Str("serialization/missing")],
+            "unknown dynamic action `serialization/missing`",
+        );
+    }
+}
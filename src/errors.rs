@@ -1,5 +1,10 @@
-//! Errors that may occur when working with 2D coordinates
+//! Errors that may occur when working with this crate
 
+use crate::axislike::SingleAxis;
+use crate::user_input::UserInput;
+use crate::ActionKind;
+
+use bevy::input::gamepad::Gamepad;
 use derive_more::{Display, Error};
 
 /// The supplied vector-like struct was too close to zero to be converted into a rotation-like type
@@ -11,3 +16,73 @@ use derive_more::{Display, Error};
 /// In almost all cases, the correct way to handle this error is to simply not change the rotation.
 #[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq)]
 pub struct NearlySingularConversion;
+
+/// The registry of [`DynamicAction`](crate::dynamic_action::DynamicAction)s is full
+///
+/// This error is produced when registering a new action once [`DynamicAction::CAPACITY`](crate::dynamic_action::DynamicAction::CAPACITY) actions have been registered.
+#[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq)]
+#[display(fmt = "no more than {} dynamic actions can be registered", capacity)]
+pub struct TooManyDynamicActions {
+    /// The maximum number of dynamic actions
+    pub capacity: usize,
+}
+
+/// A binding or configuration change was rejected by an [`InputMap`](crate::input_map::InputMap)
+///
+/// This error is produced by the fallible methods of [`InputMap`](crate::input_map::InputMap),
+/// such as [`try_insert`](crate::input_map::InputMap::try_insert) and [`try_merge`](crate::input_map::InputMap::try_merge).
+/// Its [`Display`](std::fmt::Display) implementation is suitable for explaining to players why their binding was not accepted.
+#[derive(Debug, Clone, Error, Display, PartialEq, Eq)]
+pub enum InputMapError {
+    /// Space for the binding could not be allocated
+    #[display(fmt = "there is no space left to store the binding {}", input)]
+    Capacity {
+        /// The input that could not be stored
+        input: UserInput,
+    },
+    /// The input is already bound to this action
+    #[display(fmt = "{} is already bound to this action", input)]
+    DuplicateBinding {
+        /// The input that was already present
+        input: UserInput,
+    },
+    /// An axis in the binding has a `positive_low` threshold that is lower than its `negative_low` threshold,
+    /// or one of its thresholds is not a number
+    #[display(
+        fmt = "the {:?} axis has invalid thresholds (negative: {}, positive: {})",
+        "axis.axis_type",
+        "axis.negative_low",
+        "axis.positive_low"
+    )]
+    InvalidAxisThreshold {
+        /// The axis whose thresholds are invalid
+        axis: SingleAxis,
+    },
+    /// The input cannot be bound to an action of this kind, such as when binding a [`DualAxis`](crate::axislike::DualAxis) to a button
+    #[display(fmt = "{} cannot be bound to an action of kind {:?}", input, kind)]
+    IncompatibleBinding {
+        /// The input that was rejected
+        input: UserInput,
+        /// The kind of the action that the input was bound to
+        kind: ActionKind,
+    },
+    /// The gamepad is not currently connected
+    #[display(fmt = "gamepad {} is not connected", "gamepad.id")]
+    UnknownGamepad {
+        /// The gamepad that could not be found
+        gamepad: Gamepad,
+    },
+}
+
+/// An action could not be used, as it is not ready
+///
+/// This error is produced by [`Cooldowns::trigger_if_ready`](crate::cooldown::Cooldowns::trigger_if_ready).
+#[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq, Hash)]
+pub enum CannotUseAction {
+    /// The cooldown of the action has not elapsed, and it has no charges left
+    #[display(fmt = "the action is on cooldown")]
+    OnCooldown,
+    /// The global cooldown has not elapsed
+    #[display(fmt = "the global cooldown has not elapsed")]
+    OnGlobalCooldown,
+}
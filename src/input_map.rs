@@ -1,19 +1,25 @@
 //! This module contains [`InputMap`] and its supporting methods and impls.
 
 use crate::action_state::ActionData;
+use crate::axis_filters::{AxisFilter, AxisFilterState};
+use crate::axislike::{DualAxisData, MouseMotionNormalization, VirtualAxis, VirtualDPad};
 use crate::buttonlike::ButtonState;
 use crate::clashing_inputs::ClashStrategy;
+use crate::errors::InputMapError;
+use crate::input_set::InputSet;
 use crate::input_streams::InputStreams;
-use crate::user_input::{InputKind, Modifier, UserInput};
+use crate::user_input::{
+    AggregationPolicy, ChordTiming, InputKind, Modifier, TriggerMode, UserInput,
+};
 use crate::Actionlike;
 
 use bevy::ecs::component::Component;
 use bevy::ecs::system::Resource;
-use bevy::input::gamepad::Gamepad;
+use bevy::input::gamepad::{Gamepad, Gamepads};
 use bevy::reflect::TypeUuid;
+use bevy::utils::{Duration, Instant};
 
 use core::fmt::Debug;
-use petitset::PetitSet;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::hash::Hash;
@@ -26,8 +32,8 @@ use std::marker::PhantomData;
 ///
 /// The provided input types must be able to be converted into a [`UserInput`].
 ///
-/// The maximum number of bindings (total) that can be stored for each action is 16.
-/// Insertions will silently fail if you have reached this cap.
+/// There is no limit to the number of bindings that can be stored for each action.
+/// The first 16 bindings of each action are stored inline, without any heap allocation.
 ///
 /// By default, if two actions would be triggered by a combination of buttons,
 /// and one combination is a strict subset of the other, only the larger input is registered.
@@ -76,18 +82,34 @@ use std::marker::PhantomData;
 #[derive(Resource, Component, Debug, Clone, PartialEq, Eq, TypeUuid)]
 #[uuid = "D7DECC78-8573-42FF-851A-F0344C7D05C9"]
 pub struct InputMap<A: Actionlike> {
-    /// The raw vector of [PetitSet]s used to store the input mapping,
+    /// The raw vector of [`InputSet`]s used to store the input mapping,
     /// indexed by the `Actionlike::id` of `A`
-    map: Vec<PetitSet<UserInput, 16>>,
+    map: Vec<InputSet<UserInput, 16>>,
     associated_gamepad: Option<Gamepad>,
+    /// How the [`MouseMotion`](bevy::input::mouse::MouseMotion) of mouse motion axes is converted into axis values
+    mouse_motion_normalization: MouseMotionNormalization,
+    /// The [`ChordTiming`] constraints of each action, indexed in the same way as `map`
+    chord_timing: Vec<Option<ChordTiming>>,
+    /// The [`AggregationPolicy`] of each action, indexed in the same way as `map`
+    aggregation: Vec<AggregationPolicy>,
+    /// The [`TriggerMode`] of each binding that is not pressed immediately, keyed by the index of its action
+    triggers: HashMap<(usize, UserInput), TriggerMode>,
+    /// The [`AxisFilter`]s of each binding that is filtered, keyed by the index of its action
+    filters: HashMap<(usize, UserInput), Vec<AxisFilter>>,
     marker: PhantomData<A>,
 }
 
 impl<A: Actionlike> Default for InputMap<A> {
     fn default() -> Self {
         InputMap {
-            map: A::variants().map(|_| PetitSet::default()).collect(),
+            // Dynamic action types may register more actions after the map is created
+            map: vec![InputSet::default(); A::N_VARIANTS],
             associated_gamepad: None,
+            mouse_motion_normalization: MouseMotionNormalization::default(),
+            chord_timing: vec![None; A::N_VARIANTS],
+            aggregation: vec![AggregationPolicy::default(); A::N_VARIANTS],
+            triggers: HashMap::new(),
+            filters: HashMap::new(),
             marker: PhantomData,
         }
     }
@@ -126,6 +148,14 @@ impl<A: Actionlike> InputMap<A> {
         input_map
     }
 
+    /// Creates a new [`InputMap`] containing the [`Actionlike::default_bindings`] of `A`
+    ///
+    /// When deriving [`Actionlike`], these are declared using `#[actionlike(...)]` attributes on each variant.
+    #[must_use]
+    pub fn default_bindings() -> Self {
+        InputMap::new(A::default_bindings())
+    }
+
     /// Constructs a new [`InputMap`] from a `&mut InputMap`, allowing you to insert or otherwise use it
     ///
     /// This is helpful when constructing input maps using the "builder pattern":
@@ -162,26 +192,79 @@ impl<A: Actionlike> InputMap<A> {
 impl<A: Actionlike> InputMap<A> {
     /// Insert a mapping between `input` and `action`
     ///
+    /// Duplicate bindings are ignored.
+    /// To find out whether (and why) a binding was rejected, use [`InputMap::try_insert`] instead.
+    ///
     /// # Panics
     ///
-    /// Panics if the map is full and `input` is not a duplicate.
+    /// Panics if the `input` is incompatible with the declared [`Actionlike::kind`] of the `action`.
+    #[track_caller]
     pub fn insert(&mut self, input: impl Into<UserInput>, action: A) -> &mut Self {
         let input = input.into();
+        assert_compatible_kind(&input, &action);
 
         self.map[action.index()].insert(input);
 
         self
     }
 
+    /// Insert a mapping between `input` and `action`, which presses the action according to the `trigger`
+    ///
+    /// If the binding already exists, only its [`TriggerMode`] is changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `input` is incompatible with the declared [`Actionlike::kind`] of the `action`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::gamepad::GamepadButtonType;
+    /// use bevy::utils::Duration;
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::user_input::TriggerMode;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+    /// enum Action {
+    ///     Reload,
+    ///     WeaponWheel,
+    /// }
+    ///
+    /// let mut input_map = InputMap::default();
+    /// input_map
+    ///     .insert(GamepadButtonType::RightTrigger, Action::WeaponWheel)
+    ///     // Tapping the button reloads, unless the weapon wheel consumes `Action::Reload`
+    ///     .insert_with_trigger(
+    ///         GamepadButtonType::RightTrigger,
+    ///         Action::Reload,
+    ///         TriggerMode::Click { max_duration: Duration::from_millis(250) },
+    ///     );
+    /// ```
+    #[track_caller]
+    pub fn insert_with_trigger(
+        &mut self,
+        input: impl Into<UserInput>,
+        action: A,
+        trigger: TriggerMode,
+    ) -> &mut Self {
+        let input = input.into();
+        self.insert(input.clone(), action.clone());
+        self.set_trigger(action, input, trigger);
+        self
+    }
+
     /// Insert a mapping between `input` and `action` at the provided index
     ///
     /// If a matching input already existed in the set, it will be moved to the supplied index. Any input that was previously there will be moved to the matching input’s original index.
     ///
+    /// If `index` is past the last binding of the `action`, the intervening slots are left empty.
+    ///
     /// # Panics
     ///
-    /// Panics if the map is full and `input` is not a duplicate.
+    /// Panics if the `input` is incompatible with the declared [`Actionlike::kind`] of the `action`.
+    #[track_caller]
     pub fn insert_at(&mut self, input: impl Into<UserInput>, action: A, index: usize) -> &mut Self {
         let input = input.into();
+        assert_compatible_kind(&input, &action);
 
         self.map[action.index()].insert_at(input, index);
 
@@ -193,10 +276,6 @@ impl<A: Actionlike> InputMap<A> {
     /// This method creates multiple distinct bindings.
     /// If you want to require multiple buttons to be pressed at once, use [`insert_chord`](Self::insert_chord).
     /// Any iterator that can be converted into a [`UserInput`] can be supplied.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the map is full and any of `inputs` is not a duplicate.
     pub fn insert_multiple(
         &mut self,
         input_action_pairs: impl IntoIterator<Item = (impl Into<UserInput>, A)>,
@@ -210,14 +289,10 @@ impl<A: Actionlike> InputMap<A> {
 
     /// Insert a mapping between the simultaneous combination of `buttons` and the `action` provided
     ///
-    /// Any iterator that can be converted into a [`Button`] can be supplied, but will be converted into a [`PetitSet`] for storage and use.
+    /// Any iterator that can be converted into a [`Button`] can be supplied, but will be converted into an [`InputSet`] for storage and use.
     /// Chords can also be added with the [insert](Self::insert) method, if the [`UserInput::Chord`] variant is constructed explicitly.
     ///
     /// When working with keyboard modifier keys, consider using the `insert_modified` method instead.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the map is full and `buttons` is not a duplicate.
     pub fn insert_chord(
         &mut self,
         buttons: impl IntoIterator<Item = impl Into<InputKind>>,
@@ -246,6 +321,9 @@ impl<A: Actionlike> InputMap<A> {
     /// Like usual, any duplicate bindings are ignored.
     ///
     /// If the associated gamepads do not match, the resulting associated gamepad will be set to `None`.
+    /// If both maps set a [`MouseMotionNormalization`], that of this map is kept.
+    /// If both maps set [`ChordTiming`] constraints or [`AggregationPolicy`]s for the same action,
+    /// or [`TriggerMode`]s or [`AxisFilter`]s for the same binding, those of this map are kept.
     pub fn merge(&mut self, other: &InputMap<A>) -> &mut Self {
         let associated_gamepad = if self.associated_gamepad == other.associated_gamepad {
             self.associated_gamepad
@@ -268,9 +346,147 @@ impl<A: Actionlike> InputMap<A> {
             }
         }
 
+        new_map.fill_settings_from(self);
+        new_map.fill_settings_from(other);
+
         *self = new_map;
         self
     }
+
+    /// Attempts to insert a mapping between `input` and `action`
+    ///
+    /// Unlike [`InputMap::insert`], this method reports why a binding was rejected:
+    /// - [`InputMapError::InvalidAxisThreshold`] if any axis in `input` has invalid thresholds
+    /// - [`InputMapError::DuplicateBinding`] if `input` is already bound to `action`
+    /// - [`InputMapError::Capacity`] if there was no space to store the binding
+    ///
+    /// The [`InputMap`] is unchanged if an error is returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::errors::InputMapError;
+    /// use bevy::input::keyboard::KeyCode;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    /// enum Action {
+    ///     Jump,
+    /// }
+    ///
+    /// let mut input_map = InputMap::default();
+    /// assert!(input_map.try_insert(KeyCode::Space, Action::Jump).is_ok());
+    /// assert_eq!(
+    ///     input_map.try_insert(KeyCode::Space, Action::Jump).unwrap_err(),
+    ///     InputMapError::DuplicateBinding {
+    ///         input: KeyCode::Space.into()
+    ///     }
+    /// );
+    /// ```
+    // Errors are rare, and boxing the input would make them more awkward to inspect
+    #[allow(clippy::result_large_err)]
+    pub fn try_insert(
+        &mut self,
+        input: impl Into<UserInput>,
+        action: A,
+    ) -> Result<&mut Self, InputMapError> {
+        let input = input.into();
+        validate_axis_thresholds(&input)?;
+        validate_kind(&input, &action)?;
+
+        let bindings = &mut self.map[action.index()];
+        if bindings.contains(&input) {
+            return Err(InputMapError::DuplicateBinding { input });
+        }
+
+        bindings
+            .try_insert(input)
+            .map_err(|err| InputMapError::Capacity { input: err.0 })?;
+
+        Ok(self)
+    }
+
+    /// Attempts to merge the provided [`InputMap`] into the [`InputMap`] this method was called on
+    ///
+    /// Like [`InputMap::merge`], bindings that are present in both maps are simply kept once,
+    /// and the associated gamepad is cleared if the two maps disagree.
+    /// However, every binding in `other` is checked as if by [`InputMap::try_insert`],
+    /// and the first rejected binding is returned as an error.
+    ///
+    /// The merge is all-or-nothing: the [`InputMap`] is unchanged if an error is returned.
+    #[allow(clippy::result_large_err)]
+    pub fn try_merge(&mut self, other: &InputMap<A>) -> Result<&mut Self, InputMapError> {
+        let mut merged = self.clone();
+        if merged.associated_gamepad != other.associated_gamepad {
+            merged.associated_gamepad = None;
+        }
+
+        for (inputs, action) in other.iter() {
+            for input in inputs.iter() {
+                match merged.try_insert(input.clone(), action.clone()) {
+                    Ok(_) | Err(InputMapError::DuplicateBinding { .. }) => (),
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        merged.fill_settings_from(other);
+
+        *self = merged;
+        Ok(self)
+    }
+}
+
+/// Checks that the `input` is compatible with the declared [`Actionlike::kind`] of the `action`
+#[allow(clippy::result_large_err)]
+fn validate_kind<A: Actionlike>(input: &UserInput, action: &A) -> Result<(), InputMapError> {
+    match action.kind() {
+        Some(kind) if !kind.accepts(input) => Err(InputMapError::IncompatibleBinding {
+            input: input.clone(),
+            kind,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Panics if the `input` is incompatible with the declared [`Actionlike::kind`] of the `action`
+#[track_caller]
+fn assert_compatible_kind<A: Actionlike>(input: &UserInput, action: &A) {
+    if let Err(err) = validate_kind(input, action) {
+        panic!("{err}");
+    }
+}
+
+/// Checks that every axis stored in the `input` has valid thresholds
+#[allow(clippy::result_large_err)]
+fn validate_axis_thresholds(input: &UserInput) -> Result<(), InputMapError> {
+    let validate_kind = |input_kind: &InputKind| -> Result<(), InputMapError> {
+        let axes = match input_kind {
+            InputKind::SingleAxis(axis) => vec![*axis],
+            InputKind::DualAxis(dual_axis) => vec![dual_axis.x, dual_axis.y],
+            _ => Vec::new(),
+        };
+
+        match axes.into_iter().find(|axis| !axis.has_valid_thresholds()) {
+            Some(axis) => Err(InputMapError::InvalidAxisThreshold { axis }),
+            None => Ok(()),
+        }
+    };
+
+    match input {
+        UserInput::Single(input_kind) => validate_kind(input_kind),
+        UserInput::Chord(input_kinds) => input_kinds.iter().try_for_each(validate_kind),
+        UserInput::VirtualDPad(VirtualDPad {
+            up,
+            down,
+            left,
+            right,
+        }) => [up, down, left, right]
+            .into_iter()
+            .try_for_each(validate_kind),
+        UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
+            [negative, positive].into_iter().try_for_each(validate_kind)
+        }
+    }
 }
 
 // Configuration
@@ -296,11 +512,204 @@ impl<A: Actionlike> InputMap<A> {
         self
     }
 
+    /// Assigns a particular [`Gamepad`] to the entity controlled by this input map, if it is connected
+    ///
+    /// Returns [`InputMapError::UnknownGamepad`] if the `gamepad` is not found in `gamepads`,
+    /// leaving the associated gamepad unchanged.
+    /// This is useful when restoring saved settings, as the gamepad may have been unplugged since.
+    #[allow(clippy::result_large_err)]
+    pub fn try_set_gamepad(
+        &mut self,
+        gamepad: Gamepad,
+        gamepads: &Gamepads,
+    ) -> Result<&mut Self, InputMapError> {
+        if !gamepads.contains(gamepad) {
+            return Err(InputMapError::UnknownGamepad { gamepad });
+        }
+
+        Ok(self.set_gamepad(gamepad))
+    }
+
     /// Clears any [Gamepad] associated with the entity controlled by this input map
     pub fn clear_gamepad(&mut self) -> &mut Self {
         self.associated_gamepad = None;
         self
     }
+
+    /// Fetches how [`MouseMotion`](bevy::input::mouse::MouseMotion) is converted into the values of mouse motion axes
+    #[must_use]
+    pub fn mouse_motion_normalization(&self) -> MouseMotionNormalization {
+        self.mouse_motion_normalization
+    }
+
+    /// Sets how [`MouseMotion`](bevy::input::mouse::MouseMotion) is converted into the values of mouse motion axes
+    ///
+    /// By default, mouse motion axes report the raw distance moved during each frame,
+    /// which shrinks as the frame rate grows.
+    /// See [`MouseMotionNormalization`] for frame-rate independent alternatives.
+    /// Unlike the associated gamepad, this is serialized with the rest of the map.
+    ///
+    /// # Example
+    /// ```rust
+    /// use leafwing_input_manager::axislike::{DualAxis, MouseMotionNormalization};
+    /// use leafwing_input_manager::input_map::InputMap;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash)]
+    /// enum Action {
+    ///     Look,
+    /// }
+    ///
+    /// // Moving the mouse at 1000 pixels per second tilts the camera as far as the right stick
+    /// let mut input_map = InputMap::new([
+    ///     (DualAxis::right_stick(), Action::Look),
+    ///     (DualAxis::mouse_motion(), Action::Look),
+    /// ]);
+    /// input_map.set_mouse_motion_normalization(MouseMotionNormalization::Stick { scale: 0.001 });
+    /// ```
+    pub fn set_mouse_motion_normalization(
+        &mut self,
+        normalization: MouseMotionNormalization,
+    ) -> &mut Self {
+        self.mouse_motion_normalization = normalization;
+        self
+    }
+
+    /// Fetches the [`ChordTiming`] constraints on the chords bound to `action`, if any
+    #[must_use]
+    pub fn chord_timing(&self, action: A) -> Option<ChordTiming> {
+        self.chord_timing[action.index()]
+    }
+
+    /// Constrains when the buttons of each chord bound to `action` must be pressed for the chord to press the action
+    ///
+    /// See [`ChordTiming`] for more details.
+    /// Unlike the associated gamepad, these constraints are serialized with the rest of the map.
+    pub fn set_chord_timing(&mut self, action: A, timing: ChordTiming) -> &mut Self {
+        self.chord_timing[action.index()] = Some(timing);
+        self
+    }
+
+    /// Removes any [`ChordTiming`] constraints on the chords bound to `action`
+    pub fn clear_chord_timing(&mut self, action: A) -> &mut Self {
+        self.chord_timing[action.index()] = None;
+        self
+    }
+
+    /// Fetches how the values of the bindings of `action` are combined when several of them are pressed
+    #[must_use]
+    pub fn aggregation(&self, action: A) -> AggregationPolicy {
+        self.aggregation[action.index()]
+    }
+
+    /// Sets how the `value` and `axis_pair` of the bindings of `action` are combined when several of them are pressed
+    ///
+    /// See [`AggregationPolicy`] for more details.
+    /// Unlike the associated gamepad, aggregation policies are serialized with the rest of the map.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode};
+    /// use leafwing_input_manager::input_map::InputMap;
+    /// use leafwing_input_manager::user_input::AggregationPolicy;
+    /// use leafwing_input_manager::Actionlike;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash)]
+    /// enum Action {
+    ///     Accelerate,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([
+    ///     (KeyCode::W, Action::Accelerate),
+    /// ]);
+    /// input_map
+    ///     .insert(GamepadButtonType::RightTrigger2, Action::Accelerate)
+    ///     .set_aggregation(Action::Accelerate, AggregationPolicy::MaxMagnitude);
+    /// ```
+    pub fn set_aggregation(&mut self, action: A, policy: AggregationPolicy) -> &mut Self {
+        self.aggregation[action.index()] = policy;
+        self
+    }
+
+    /// Fetches the [`TriggerMode`] of the binding between `input` and `action`
+    ///
+    /// Bindings are [`TriggerMode::Press`] unless otherwise specified.
+    #[must_use]
+    pub fn trigger(&self, action: A, input: &UserInput) -> TriggerMode {
+        self.triggers
+            .get(&(action.index(), input.clone()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the [`TriggerMode`] of the binding between `input` and `action`
+    ///
+    /// This has no effect unless `input` is bound to `action`.
+    /// Unlike the associated gamepad, trigger modes are serialized with the rest of the map.
+    pub fn set_trigger(
+        &mut self,
+        action: A,
+        input: impl Into<UserInput>,
+        trigger: TriggerMode,
+    ) -> &mut Self {
+        let input = input.into();
+        if !self.get(action.clone()).contains(&input) {
+            return self;
+        }
+
+        let key = (action.index(), input);
+        if trigger == TriggerMode::Press {
+            self.triggers.remove(&key);
+        } else {
+            self.triggers.insert(key, trigger);
+        }
+        self
+    }
+
+    /// Fetches the [`AxisFilter`]s applied to the binding between `input` and `action`, in the order they are applied
+    ///
+    /// Bindings are not filtered unless otherwise specified.
+    #[must_use]
+    pub fn filters(&self, action: A, input: &UserInput) -> &[AxisFilter] {
+        self.filters
+            .get(&(action.index(), input.clone()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Are any of the bindings of this map filtered?
+    #[must_use]
+    pub(crate) fn has_filters(&self) -> bool {
+        !self.filters.is_empty()
+    }
+
+    /// Sets the [`AxisFilter`]s applied to the `value` and `axis_pair` of the binding between `input` and `action`,
+    /// replacing any previous filters
+    ///
+    /// Filters are applied in order, using the [`AxisFilterState`] stored alongside this map.
+    /// See the [`axis_filters`](crate::axis_filters) module for more details.
+    /// This has no effect unless `input` is bound to `action`.
+    /// Unlike the associated gamepad, filters are serialized with the rest of the map.
+    pub fn set_filters(
+        &mut self,
+        action: A,
+        input: impl Into<UserInput>,
+        filters: impl IntoIterator<Item = AxisFilter>,
+    ) -> &mut Self {
+        let input = input.into();
+        if !self.get(action.clone()).contains(&input) {
+            return self;
+        }
+
+        let key = (action.index(), input);
+        let filters: Vec<AxisFilter> = filters.into_iter().collect();
+        if filters.is_empty() {
+            self.filters.remove(&key);
+        } else {
+            self.filters.insert(key, filters);
+        }
+        self
+    }
 }
 
 // Check whether buttons are pressed
@@ -324,68 +733,229 @@ impl<A: Actionlike> InputMap<A> {
     ///
     /// Accounts for clashing inputs according to the [`ClashStrategy`].
     /// The position in each vector corresponds to `Actionlike::index()`.
+    /// [`AxisFilter`]s are ignored: use [`InputMap::which_pressed_filtered`] to apply them.
     #[must_use]
     pub fn which_pressed(
         &self,
         input_streams: &InputStreams,
         clash_strategy: ClashStrategy,
     ) -> Vec<ActionData> {
+        self.collect_action_data(input_streams, clash_strategy, None)
+            .0
+    }
+
+    /// Like [`InputMap::which_pressed`], but also applies the [`AxisFilter`]s of each binding,
+    /// advancing them by `delta` and storing their outputs in `filter_state`
+    #[must_use]
+    pub fn which_pressed_filtered(
+        &self,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+        filter_state: &mut AxisFilterState<A>,
+        delta: Duration,
+    ) -> Vec<ActionData> {
+        self.collect_action_data(input_streams, clash_strategy, Some((filter_state, delta)))
+            .0
+    }
+
+    /// Returns the [`TriggerMode`] of the held bindings of each action that will press it once they are released, if any
+    ///
+    /// Pass this to [`ActionState::update_held_triggers`](crate::action_state::ActionState::update_held_triggers)
+    /// before each update, so that releasing those bindings presses their action.
+    /// Accounts for clashing inputs according to the [`ClashStrategy`].
+    /// The position in the vector corresponds to `Actionlike::index()`.
+    #[must_use]
+    pub fn held_triggers(
+        &self,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+    ) -> Vec<Option<TriggerMode>> {
+        if self.triggers.is_empty() {
+            return vec![None; A::N_VARIANTS];
+        }
+
+        self.collect_action_data(input_streams, clash_strategy, None)
+            .1
+    }
+
+    fn collect_action_data(
+        &self,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+        mut filtering: Option<(&mut AxisFilterState<A>, Duration)>,
+    ) -> (Vec<ActionData>, Vec<Option<TriggerMode>>) {
         let mut action_data = vec![ActionData::default(); A::N_VARIANTS];
+        let mut held_triggers = vec![None; A::N_VARIANTS];
 
         // Generate the raw action presses
         for action in A::variants() {
-            let mut inputs = Vec::new();
+            let mut bindings = Vec::new();
+            let chord_timing = self.chord_timing(action.clone());
 
             for input in self.get(action.clone()).iter() {
-                let action = &mut action_data[action.index()];
+                let trigger = self.trigger(action.clone(), input);
+                let filters = self.filters(action.clone(), input);
+                let action_index = action.index();
 
-                // Merge axis pair into action data
-                let axis_pair = input_streams.input_axis_pair(input);
-                if let Some(axis_pair) = axis_pair {
-                    if let Some(current_axis_pair) = &mut action.axis_pair {
-                        *current_axis_pair = current_axis_pair.merged_with(axis_pair);
-                    } else {
-                        action.axis_pair = Some(axis_pair);
+                let timing_satisfied = match (input, chord_timing) {
+                    (UserInput::Chord(buttons), Some(timing)) => {
+                        input_streams.chord_timing_satisfied(buttons, timing)
                     }
+                    _ => true,
+                };
+                let pressed = timing_satisfied && input_streams.input_pressed(input);
+                // Bindings with other trigger modes only press the action once they are released
+                let presses_action = pressed && trigger == TriggerMode::Press;
+
+                // Bindings that do not press the action do not move it either
+                let mut axis_pair = input_streams.input_axis_pair(input);
+                let mut value = if presses_action {
+                    input_streams.input_value(input)
+                } else {
+                    axis_pair = axis_pair.map(|_| DualAxisData::default());
+                    0.0
+                };
+
+                // Filters run every frame, so that they can settle while the binding is released
+                if let (Some((filter_state, delta)), false) = (&mut filtering, filters.is_empty()) {
+                    (value, axis_pair) =
+                        filter_state.apply(action_index, input, filters, value, axis_pair, *delta);
                 }
 
-                if input_streams.input_pressed(input) {
-                    inputs.push(input.clone());
-
-                    action.value += input_streams.input_value(input);
+                if pressed && !presses_action {
+                    held_triggers[action_index].get_or_insert(trigger);
                 }
+
+                bindings.push(BindingOutput {
+                    input,
+                    pressed: presses_action,
+                    value,
+                    axis_pair,
+                });
             }
 
-            if !inputs.is_empty() {
-                action_data[action.index()].state = ButtonState::JustPressed;
+            let policy = self.aggregation(action.clone());
+            let action = &mut action_data[action.index()];
+            if bindings.iter().any(|binding| binding.pressed) {
+                action.state = ButtonState::JustPressed;
             }
+
+            (action.value, action.axis_pair) = aggregate(policy, &bindings, input_streams);
         }
 
         // Handle clashing inputs, possibly removing some pressed actions from the list
+        let pressed: Vec<bool> = action_data
+            .iter()
+            .map(|data| data.state.pressed())
+            .collect();
         self.handle_clashes(&mut action_data, input_streams, clash_strategy);
 
-        action_data
+        // The held bindings of culled actions are culled along with them
+        for (index, was_pressed) in pressed.into_iter().enumerate() {
+            if was_pressed && !action_data[index].state.pressed() {
+                held_triggers[index] = None;
+            }
+        }
+
+        (action_data, held_triggers)
+    }
+}
+
+/// The `value` and `axis_pair` of a single binding, before they are combined with those of the other bindings of its action
+struct BindingOutput<'a> {
+    input: &'a UserInput,
+    /// Is this binding pressed, and does it press its action immediately?
+    pressed: bool,
+    /// This is zero unless the binding is `pressed`, or its filters are still settling
+    value: f32,
+    /// Like the `value`, this is zero unless the binding is `pressed`, or its filters are still settling
+    axis_pair: Option<DualAxisData>,
+}
+
+/// Combines the `value` and `axis_pair` of each of the `bindings` of an action, according to the `policy`
+///
+/// Both are taken from the same bindings: every binding for the policies that combine them,
+/// or the selected binding for the policies that select one.
+fn aggregate(
+    policy: AggregationPolicy,
+    bindings: &[BindingOutput],
+    input_streams: &InputStreams,
+) -> (f32, Option<DualAxisData>) {
+    let pressed = bindings.iter().filter(|binding| binding.pressed);
+    let sum = || {
+        (
+            bindings.iter().map(|binding| binding.value).sum(),
+            bindings
+                .iter()
+                .filter_map(|binding| binding.axis_pair)
+                .reduce(|merged, axis_pair| merged.merged_with(axis_pair)),
+        )
+    };
+
+    // Selects the first binding when several are equally good
+    let select = |winner: Option<&BindingOutput>| match winner {
+        Some(binding) => (binding.value, binding.axis_pair),
+        None => sum(),
+    };
+
+    match policy {
+        AggregationPolicy::Sum => sum(),
+        AggregationPolicy::SumClamped => {
+            let (value, mut axis_pair): (f32, _) = sum();
+            if let Some(axis_pair) = &mut axis_pair {
+                axis_pair.clamp_length(1.0);
+            }
+            (value.clamp(-1.0, 1.0), axis_pair)
+        }
+        AggregationPolicy::MaxMagnitude => {
+            // Bindings with an axis pair are measured by its length, as their value may only be one of its axes
+            let magnitude = |binding: &BindingOutput| match binding.axis_pair {
+                Some(axis_pair) => axis_pair.length(),
+                None => binding.value.abs(),
+            };
+
+            select(bindings.iter().reduce(|max, binding| {
+                if magnitude(binding) > magnitude(max) {
+                    binding
+                } else {
+                    max
+                }
+            }))
+        }
+        AggregationPolicy::FirstBinding => select(bindings.iter().find(|binding| binding.pressed)),
+        AggregationPolicy::MostRecentDevice => select(
+            pressed
+                .map(|binding| (input_streams.device_last_used(binding.input), binding))
+                .reduce(|latest, (last_used, binding)| {
+                    if last_used > latest.0 {
+                        (last_used, binding)
+                    } else {
+                        latest
+                    }
+                })
+                .map(|(_, binding): (Option<Instant>, _)| binding),
+        ),
     }
 }
 
 // Utilities
 impl<A: Actionlike> InputMap<A> {
     /// Returns an iterator over actions with their inputs
-    pub fn iter(&self) -> impl Iterator<Item = (&PetitSet<UserInput, 16>, A)> {
+    pub fn iter(&self) -> impl Iterator<Item = (&InputSet<UserInput, 16>, A)> {
         self.map
             .iter()
             .enumerate()
-            .map(|(action_index, inputs)| (inputs, A::get_at(action_index).unwrap()))
+            .filter_map(|(action_index, inputs)| Some((inputs, A::get_at(action_index)?)))
     }
 
     /// Returns an iterator over all mapped inputs
-    pub fn iter_inputs(&self) -> impl Iterator<Item = &PetitSet<UserInput, 16>> {
+    pub fn iter_inputs(&self) -> impl Iterator<Item = &InputSet<UserInput, 16>> {
         self.map.iter()
     }
 
     /// Returns the `action` mappings
     #[must_use]
-    pub fn get(&self, action: A) -> &PetitSet<UserInput, 16> {
+    pub fn get(&self, action: A) -> &InputSet<UserInput, 16> {
         &self.map[action.index()]
     }
 
@@ -412,63 +982,262 @@ impl<A: Actionlike> InputMap<A> {
     /// Clears all inputs registered for the `action`
     pub fn clear_action(&mut self, action: A) {
         self.map[action.index()].clear();
+        self.forget_removed_bindings(action);
     }
 
     /// Removes the input for the `action` at the provided index
     ///
     /// Returns `true` if an element was found.
     pub fn remove_at(&mut self, action: A, index: usize) -> bool {
-        self.map[action.index()].remove_at(index)
+        let removed = self.map[action.index()].remove_at(index);
+        self.forget_removed_bindings(action);
+        removed
     }
 
     /// Removes the input for the `action`, if it exists
     ///
     /// Returns [`Some`] with index if the input was found, or [`None`] if no matching input was found.
     pub fn remove(&mut self, action: A, input: impl Into<UserInput>) -> Option<usize> {
-        self.map[action.index()].remove(&input.into())
+        let removed = self.map[action.index()].remove(&input.into());
+        self.forget_removed_bindings(action);
+        removed
+    }
+
+    /// Copies each setting of `other` that is left at its default in this map
+    ///
+    /// The [`TriggerMode`]s and [`AxisFilter`]s of bindings that are not in this map are skipped.
+    pub(crate) fn fill_settings_from(&mut self, other: &InputMap<A>) {
+        if self.mouse_motion_normalization == MouseMotionNormalization::default() {
+            self.mouse_motion_normalization = other.mouse_motion_normalization;
+        }
+
+        for action in A::variants() {
+            if self.chord_timing(action.clone()).is_none() {
+                self.chord_timing[action.index()] = other.chord_timing(action.clone());
+            }
+
+            if self.aggregation(action.clone()) == AggregationPolicy::default() {
+                self.aggregation[action.index()] = other.aggregation(action.clone());
+            }
+        }
+
+        let map = &self.map;
+        let is_bound = |(index, input): &(usize, UserInput)| {
+            map.get(*index).is_some_and(|inputs| inputs.contains(input))
+        };
+
+        for (key, trigger) in other.triggers.iter().filter(|(key, _)| is_bound(key)) {
+            self.triggers.entry(key.clone()).or_insert(*trigger);
+        }
+
+        for (key, filters) in other.filters.iter().filter(|(key, _)| is_bound(key)) {
+            self.filters
+                .entry(key.clone())
+                .or_insert_with(|| filters.clone());
+        }
+    }
+
+    /// Removes the [`TriggerMode`]s and [`AxisFilter`]s of bindings to `action` that no longer exist,
+    /// so that they are not reused if the binding is added again
+    fn forget_removed_bindings(&mut self, action: A) {
+        let action_index = action.index();
+        let inputs = &self.map[action_index];
+        self.triggers
+            .retain(|(index, input), _| *index != action_index || inputs.contains(input));
+        self.filters
+            .retain(|(index, input), _| *index != action_index || inputs.contains(input));
     }
 }
 
-impl<A: Actionlike> From<HashMap<A, Vec<UserInput>>> for InputMap<A> {
-    /// Create `InputMap<A>` from `HashMap<A, Vec<UserInput>>`
+// Diffing
+impl<A: Actionlike> InputMap<A> {
+    /// Computes the bindings that must be added and removed to turn this [`InputMap`] into `customized`
     ///
-    /// # Panics
+    /// This is intended for storing only the changes a player has made to your default bindings:
+    /// call this method on the defaults, and save the resulting [`InputMapDiff`].
+    /// Settings such as [`ChordTiming`] constraints, [`AggregationPolicy`]s, [`TriggerMode`]s, [`AxisFilter`]s
+    /// and the [`MouseMotionNormalization`] are compared too, and recorded as [`SettingChange`]s.
+    /// The settings of removed bindings are not recorded, as they are removed along with the binding.
+    /// Associated gamepads are not compared.
     ///
-    /// Panics if the any value in map contains more than 16 distinct inputs.
     /// # Example
     /// ```rust
     /// use leafwing_input_manager::input_map::InputMap;
-    /// use leafwing_input_manager::user_input::UserInput;
     /// use leafwing_input_manager::Actionlike;
     /// use bevy::input::keyboard::KeyCode;
     ///
-    /// use std::collections::HashMap;
-    ///
     /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash)]
     /// enum Action {
     ///     Run,
     ///     Jump,
     /// }
-    /// let mut map: HashMap<Action, Vec<UserInput>> = HashMap::default();
-    /// map.insert(
-    ///     Action::Run,
-    ///     vec![KeyCode::LShift.into(), KeyCode::RShift.into()],
-    /// );
-    /// let input_map = InputMap::from(map);
+    ///
+    /// let defaults = InputMap::new([(KeyCode::LShift, Action::Run), (KeyCode::Space, Action::Jump)]);
+    ///
+    /// let mut customized = defaults.clone();
+    /// customized.remove(Action::Jump, KeyCode::Space);
+    /// customized.insert(KeyCode::W, Action::Jump);
+    /// let diff = defaults.diff(&customized);
+    ///
+    /// // A later release changes the default bindings
+    /// let mut new_defaults = InputMap::new([(KeyCode::LControl, Action::Run), (KeyCode::Space, Action::Jump)]);
+    /// new_defaults.apply_diff(&diff);
+    ///
+    /// // The new default is kept, but the player's changes are preserved
+    /// assert!(new_defaults.get(Action::Run).contains(&KeyCode::LControl.into()));
+    /// assert!(!new_defaults.get(Action::Jump).contains(&KeyCode::Space.into()));
+    /// assert!(new_defaults.get(Action::Jump).contains(&KeyCode::W.into()));
     /// ```
-    fn from(map: HashMap<A, Vec<UserInput>>) -> Self {
-        map.iter()
-            .flat_map(|(action, inputs)| inputs.iter().map(|input| (action.clone(), input.clone())))
-            .collect()
-    }
+    #[must_use]
+    pub fn diff(&self, customized: &InputMap<A>) -> InputMapDiff<A> {
+        let mut diff = InputMapDiff::default();
+
+        for action in A::variants() {
+            let original_inputs = self.get(action.clone());
+            let customized_inputs = customized.get(action.clone());
+
+            for input in customized_inputs.iter() {
+                if !original_inputs.contains(input) {
+                    diff.added.insert(input.clone(), action.clone());
+                }
+            }
+
+            for input in original_inputs.iter() {
+                if !customized_inputs.contains(input) {
+                    diff.removed.insert(input.clone(), action.clone());
+                }
+            }
+
+            let timing = customized.chord_timing(action.clone());
+            if timing != self.chord_timing(action.clone()) {
+                diff.settings.push(SettingChange::ChordTiming {
+                    action: action.clone(),
+                    timing,
+                });
+            }
+
+            let policy = customized.aggregation(action.clone());
+            if policy != self.aggregation(action.clone()) {
+                diff.settings.push(SettingChange::Aggregation {
+                    action: action.clone(),
+                    policy,
+                });
+            }
+
+            for input in customized_inputs.iter() {
+                let trigger = customized.trigger(action.clone(), input);
+                if trigger != self.trigger(action.clone(), input) {
+                    diff.settings.push(SettingChange::Trigger {
+                        action: action.clone(),
+                        input: input.clone(),
+                        trigger,
+                    });
+                }
+
+                let filters = customized.filters(action.clone(), input);
+                if filters != self.filters(action.clone(), input) {
+                    diff.settings.push(SettingChange::Filters {
+                        action: action.clone(),
+                        input: input.clone(),
+                        filters: filters.to_vec(),
+                    });
+                }
+            }
+        }
+
+        if customized.mouse_motion_normalization != self.mouse_motion_normalization {
+            diff.settings.push(SettingChange::MouseMotionNormalization(
+                customized.mouse_motion_normalization,
+            ));
+        }
+
+        diff
+    }
+
+    /// Applies the changes stored in an [`InputMapDiff`] to this [`InputMap`]
+    ///
+    /// Removed bindings that no longer exist and added bindings that already exist are ignored,
+    /// so diffs can be safely applied on top of a different [`InputMap`] than the one they were computed from.
+    /// Removed bindings leave their slot empty, and added bindings fill the first empty slot for their action.
+    /// Changes to the settings of bindings that no longer exist are ignored in the same way.
+    /// The associated gamepad is left unchanged.
+    pub fn apply_diff(&mut self, diff: &InputMapDiff<A>) -> &mut Self {
+        for (inputs, action) in diff.removed.iter() {
+            for input in inputs.iter() {
+                self.remove(action.clone(), input.clone());
+            }
+        }
+
+        for (inputs, action) in diff.added.iter() {
+            for input in inputs.iter() {
+                self.insert(input.clone(), action.clone());
+            }
+        }
+
+        for change in diff.settings.iter() {
+            match change {
+                SettingChange::ChordTiming { action, timing } => {
+                    self.chord_timing[action.index()] = *timing;
+                }
+                SettingChange::Aggregation { action, policy } => {
+                    self.set_aggregation(action.clone(), *policy);
+                }
+                SettingChange::Trigger {
+                    action,
+                    input,
+                    trigger,
+                } => {
+                    self.set_trigger(action.clone(), input.clone(), *trigger);
+                }
+                SettingChange::Filters {
+                    action,
+                    input,
+                    filters,
+                } => {
+                    self.set_filters(action.clone(), input.clone(), filters.iter().copied());
+                }
+                SettingChange::MouseMotionNormalization(normalization) => {
+                    self.set_mouse_motion_normalization(*normalization);
+                }
+            }
+        }
+
+        self
+    }
 }
 
-impl<A: Actionlike> FromIterator<(A, UserInput)> for InputMap<A> {
-    /// Create `InputMap<A>` from iterator with item type `(A, UserInput)`
+impl<A: Actionlike> From<HashMap<A, Vec<UserInput>>> for InputMap<A> {
+    /// Create `InputMap<A>` from `HashMap<A, Vec<UserInput>>`
+    /// # Example
+    /// ```rust
+    /// use leafwing_input_manager::input_map::InputMap;
+    /// use leafwing_input_manager::user_input::UserInput;
+    /// use leafwing_input_manager::Actionlike;
+    /// use bevy::input::keyboard::KeyCode;
     ///
-    /// # Panics
+    /// use std::collections::HashMap;
     ///
-    /// Panics if there are more than 16 distinct inputs for the same action.
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    /// let mut map: HashMap<Action, Vec<UserInput>> = HashMap::default();
+    /// map.insert(
+    ///     Action::Run,
+    ///     vec![KeyCode::LShift.into(), KeyCode::RShift.into()],
+    /// );
+    /// let input_map = InputMap::from(map);
+    /// ```
+    fn from(map: HashMap<A, Vec<UserInput>>) -> Self {
+        map.iter()
+            .flat_map(|(action, inputs)| inputs.iter().map(|input| (action.clone(), input.clone())))
+            .collect()
+    }
+}
+
+impl<A: Actionlike> FromIterator<(A, UserInput)> for InputMap<A> {
+    /// Create `InputMap<A>` from iterator with item type `(A, UserInput)`
     fn from_iter<T: IntoIterator<Item = (A, UserInput)>>(iter: T) -> Self {
         InputMap::new(iter.into_iter().map(|(action, input)| (input, action)))
     }
@@ -485,7 +1254,8 @@ where
         use serde::ser::SerializeStruct;
         use std::collections::BTreeMap;
 
-        let mut input_map = serializer.serialize_struct("InputMap", 1)?;
+        let mut input_map =
+            serializer.serialize_struct("InputMap", 1 + Self::SERIALIZED_SETTINGS_LEN)?;
         input_map.serialize_field(
             "map",
             &self
@@ -493,10 +1263,149 @@ where
                 .map(|(set, action)| (action, set.iter().collect()))
                 .collect::<BTreeMap<A, Vec<&UserInput>>>(),
         )?;
+        self.serialize_settings(&mut input_map)?;
         input_map.end()
     }
 }
 
+// Serialization of settings
+impl<A: Actionlike> InputMap<A> {
+    /// The number of settings that [`InputMap::serialize_settings`] serializes as fields
+    pub(crate) const SERIALIZED_SETTINGS_LEN: usize = 5;
+
+    /// Serializes each setting other than the bindings and associated gamepad as a field, keyed by action
+    ///
+    /// Every setting is serialized, even when left at its default,
+    /// so that formats which are not self-describing can read the fields back by position.
+    /// The [`TriggerMode`]s and [`AxisFilter`]s of each action are listed in the order of its bindings.
+    pub(crate) fn serialize_settings<S>(&self, state: &mut S) -> Result<(), S::Error>
+    where
+        S: serde::ser::SerializeStruct,
+        A: Serialize + Ord,
+    {
+        use std::collections::BTreeMap;
+
+        let chord_timing: BTreeMap<A, ChordTiming> = A::variants()
+            .filter_map(|action| Some((action.clone(), self.chord_timing(action)?)))
+            .collect();
+        state.serialize_field("chord_timing", &chord_timing)?;
+
+        let aggregation: BTreeMap<A, AggregationPolicy> = A::variants()
+            .map(|action| (action.clone(), self.aggregation(action)))
+            .filter(|(_, policy)| *policy != AggregationPolicy::default())
+            .collect();
+        state.serialize_field("aggregation", &aggregation)?;
+
+        let triggers: BTreeMap<A, Vec<(&UserInput, TriggerMode)>> = A::variants()
+            .map(|action| {
+                let triggers = self
+                    .get(action.clone())
+                    .iter()
+                    .filter_map(|input| {
+                        let trigger = self.triggers.get(&(action.index(), input.clone()))?;
+                        Some((input, *trigger))
+                    })
+                    .collect();
+                (action, triggers)
+            })
+            .filter(|(_, triggers): &(A, Vec<_>)| !triggers.is_empty())
+            .collect();
+        state.serialize_field("triggers", &triggers)?;
+
+        let filters: BTreeMap<A, Vec<(&UserInput, &[AxisFilter])>> = A::variants()
+            .map(|action| {
+                let filters = self
+                    .get(action.clone())
+                    .iter()
+                    .filter_map(|input| {
+                        let filters = self.filters.get(&(action.index(), input.clone()))?;
+                        Some((input, filters.as_slice()))
+                    })
+                    .collect();
+                (action, filters)
+            })
+            .filter(|(_, filters): &(A, Vec<_>)| !filters.is_empty())
+            .collect();
+        state.serialize_field("filters", &filters)?;
+
+        state.serialize_field(
+            "mouse_motion_normalization",
+            &self.mouse_motion_normalization,
+        )
+    }
+}
+
+/// The settings of an [`InputMap`] other than its bindings and associated gamepad, as deserialized
+///
+/// Actions are identified by keys of type `K`, which are resolved into actions by [`DeserializedSettings::apply`].
+#[derive(Debug)]
+pub(crate) struct DeserializedSettings<K> {
+    pub(crate) chord_timing: HashMap<K, ChordTiming>,
+    pub(crate) aggregation: HashMap<K, AggregationPolicy>,
+    pub(crate) triggers: HashMap<K, Vec<(UserInput, TriggerMode)>>,
+    pub(crate) filters: HashMap<K, Vec<(UserInput, Vec<AxisFilter>)>>,
+    pub(crate) mouse_motion_normalization: MouseMotionNormalization,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<K> Default for DeserializedSettings<K> {
+    fn default() -> Self {
+        DeserializedSettings {
+            chord_timing: HashMap::new(),
+            aggregation: HashMap::new(),
+            triggers: HashMap::new(),
+            filters: HashMap::new(),
+            mouse_motion_normalization: MouseMotionNormalization::default(),
+        }
+    }
+}
+
+impl<K> DeserializedSettings<K> {
+    /// Applies these settings to the `input_map`, using `resolve` to find the action of each key
+    ///
+    /// Keys that do not resolve to an action are skipped,
+    /// as are the [`TriggerMode`]s and [`AxisFilter`]s of bindings that are not in the `input_map`.
+    pub(crate) fn apply<A: Actionlike>(
+        self,
+        input_map: &mut InputMap<A>,
+        mut resolve: impl FnMut(K) -> Option<A>,
+    ) {
+        input_map.set_mouse_motion_normalization(self.mouse_motion_normalization);
+
+        for (key, timing) in self.chord_timing {
+            if let Some(action) = resolve(key) {
+                input_map.set_chord_timing(action, timing);
+            }
+        }
+
+        for (key, policy) in self.aggregation {
+            if let Some(action) = resolve(key) {
+                input_map.set_aggregation(action, policy);
+            }
+        }
+
+        for (key, triggers) in self.triggers {
+            let Some(action) = resolve(key) else {
+                continue;
+            };
+
+            for (input, trigger) in triggers {
+                input_map.set_trigger(action.clone(), input, trigger);
+            }
+        }
+
+        for (key, filters) in self.filters {
+            let Some(action) = resolve(key) else {
+                continue;
+            };
+
+            for (input, filters) in filters {
+                input_map.set_filters(action.clone(), input, filters);
+            }
+        }
+    }
+}
+
 impl<'de, A> Deserialize<'de> for InputMap<A>
 where
     A: Actionlike + Deserialize<'de> + Eq + Hash,
@@ -508,9 +1417,14 @@ where
         use serde::de::Visitor;
 
         #[derive(Deserialize, PartialEq)]
-        #[serde(field_identifier, rename_all = "lowercase")]
+        #[serde(field_identifier, rename_all = "snake_case")]
         enum Field {
             Map,
+            ChordTiming,
+            Aggregation,
+            Triggers,
+            Filters,
+            MouseMotionNormalization,
         }
 
         struct InputMapVisitor<'de, A: Actionlike + Deserialize<'de>> {
@@ -532,32 +1446,157 @@ where
                 S: serde::de::SeqAccess<'de>,
             {
                 let map = seq.next_element::<HashMap<A, Vec<UserInput>>>()?;
-                map.ok_or_else(|| {
+                let map = map.ok_or_else(|| {
                     serde::de::Error::invalid_length(0, &"one argument with type `map`")
-                })
-                .map(InputMap::from)
+                })?;
+
+                // Settings were not serialized by older versions
+                let settings = DeserializedSettings {
+                    chord_timing: seq.next_element()?.unwrap_or_default(),
+                    aggregation: seq.next_element()?.unwrap_or_default(),
+                    triggers: seq.next_element()?.unwrap_or_default(),
+                    filters: seq.next_element()?.unwrap_or_default(),
+                    mouse_motion_normalization: seq.next_element()?.unwrap_or_default(),
+                };
+                checked_input_map(map, settings)
             }
 
             fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
             where
                 M: serde::de::MapAccess<'de>,
             {
-                map.next_key::<Field>()?
-                    .filter(|key| *key == Field::Map)
-                    .ok_or_else(|| serde::de::Error::missing_field("map"))?;
-                let value = map.next_value::<HashMap<A, Vec<UserInput>>>()?;
-                Ok(value.into())
+                let mut bindings = None;
+                let mut settings = DeserializedSettings::default();
+                while let Some(key) = map.next_key::<Field>()? {
+                    match key {
+                        Field::Map => bindings = Some(map.next_value()?),
+                        Field::ChordTiming => settings.chord_timing = map.next_value()?,
+                        Field::Aggregation => settings.aggregation = map.next_value()?,
+                        Field::Triggers => settings.triggers = map.next_value()?,
+                        Field::Filters => settings.filters = map.next_value()?,
+                        Field::MouseMotionNormalization => {
+                            settings.mouse_motion_normalization = map.next_value()?;
+                        }
+                    }
+                }
+
+                let bindings = bindings.ok_or_else(|| serde::de::Error::missing_field("map"))?;
+                checked_input_map(bindings, settings)
             }
         }
 
+        /// Fails to deserialize, rather than panicking, if a binding is incompatible with the kind of its action
+        fn checked_input_map<A: Actionlike, E: serde::de::Error>(
+            map: HashMap<A, Vec<UserInput>>,
+            settings: DeserializedSettings<A>,
+        ) -> Result<InputMap<A>, E> {
+            for (action, inputs) in map.iter() {
+                for input in inputs {
+                    validate_kind(input, action).map_err(E::custom)?;
+                }
+            }
+
+            let mut input_map: InputMap<A> = map.into();
+            settings.apply(&mut input_map, Some);
+            Ok(input_map)
+        }
+
         let visitor = InputMapVisitor {
             marker: PhantomData,
         };
-        const FIELDS: &[&str] = &["map"];
+        const FIELDS: &[&str] = &[
+            "map",
+            "chord_timing",
+            "aggregation",
+            "triggers",
+            "filters",
+            "mouse_motion_normalization",
+        ];
         deserializer.deserialize_struct("InputMap", FIELDS, visitor)
     }
 }
 
+/// The bindings that were added to and removed from an [`InputMap`]
+///
+/// Created by [`InputMap::diff`], and reapplied using [`InputMap::apply_diff`].
+/// This is serialized as a pair of [`InputMap`]s and a list of [`SettingChange`]s,
+/// making it easy to store only the changes that players have made to your default bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "A: Serialize, InputMap<A>: Serialize",
+    deserialize = "A: Deserialize<'de>, InputMap<A>: Deserialize<'de>"
+))]
+pub struct InputMapDiff<A: Actionlike> {
+    /// The bindings that were added
+    pub added: InputMap<A>,
+    /// The bindings that were removed
+    pub removed: InputMap<A>,
+    /// The settings that were changed, which may belong to added bindings
+    #[serde(default)]
+    pub settings: Vec<SettingChange<A>>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Actionlike> Default for InputMapDiff<A> {
+    fn default() -> Self {
+        InputMapDiff {
+            added: InputMap::default(),
+            removed: InputMap::default(),
+            settings: Vec::new(),
+        }
+    }
+}
+
+impl<A: Actionlike> InputMapDiff<A> {
+    /// Does this diff leave the [`InputMap`] unchanged?
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.settings.is_empty()
+    }
+}
+
+/// A change to one of the settings of an [`InputMap`], recorded in an [`InputMapDiff`]
+///
+/// Each variant stores the new value of the setting, which may be its default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettingChange<A> {
+    /// The [`ChordTiming`] constraints of `action` were changed, see [`InputMap::set_chord_timing`]
+    ChordTiming {
+        /// The action whose chords are constrained
+        action: A,
+        /// The new constraints, if any
+        timing: Option<ChordTiming>,
+    },
+    /// The [`AggregationPolicy`] of `action` was changed, see [`InputMap::set_aggregation`]
+    Aggregation {
+        /// The action whose bindings are combined
+        action: A,
+        /// The new policy
+        policy: AggregationPolicy,
+    },
+    /// The [`TriggerMode`] of the binding between `input` and `action` was changed, see [`InputMap::set_trigger`]
+    Trigger {
+        /// The action of the binding
+        action: A,
+        /// The input of the binding
+        input: UserInput,
+        /// The new trigger mode
+        trigger: TriggerMode,
+    },
+    /// The [`AxisFilter`]s of the binding between `input` and `action` were changed, see [`InputMap::set_filters`]
+    Filters {
+        /// The action of the binding
+        action: A,
+        /// The input of the binding
+        input: UserInput,
+        /// The new filters, in the order they are applied
+        filters: Vec<AxisFilter>,
+    },
+    /// The [`MouseMotionNormalization`] was changed, see [`InputMap::set_mouse_motion_normalization`]
+    MouseMotionNormalization(MouseMotionNormalization),
+}
+
 mod tests {
     use serde::{Deserialize, Serialize};
 
@@ -573,32 +1612,58 @@ mod tests {
         Hide,
     }
 
+    #[cfg(test)]
+    /// The tokens of the settings of an [`InputMap`] that are left at their defaults
+    fn default_settings_tokens() -> Vec<serde_test::Token> {
+        use serde_test::Token;
+
+        vec![
+            Token::Str("chord_timing"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("aggregation"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("triggers"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("filters"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("mouse_motion_normalization"),
+            Token::UnitVariant {
+                name: "MouseMotionNormalization",
+                variant: "Raw",
+            },
+        ]
+    }
+
     #[test]
     fn insertion_idempotency() {
+        use crate::input_set::InputSet;
         use bevy::input::keyboard::KeyCode;
-        use petitset::PetitSet;
 
         let mut input_map = InputMap::<Action>::default();
         input_map.insert(KeyCode::Space, Action::Run);
 
         assert_eq!(
             *input_map.get(Action::Run),
-            PetitSet::<UserInput, 16>::from_iter([KeyCode::Space.into()])
+            InputSet::<UserInput, 16>::from_iter([KeyCode::Space.into()])
         );
 
         // Duplicate insertions should not change anything
         input_map.insert(KeyCode::Space, Action::Run);
         assert_eq!(
             *input_map.get(Action::Run),
-            PetitSet::<UserInput, 16>::from_iter([KeyCode::Space.into()])
+            InputSet::<UserInput, 16>::from_iter([KeyCode::Space.into()])
         );
     }
 
     #[test]
     fn multiple_insertion() {
+        use crate::input_set::InputSet;
         use crate::user_input::UserInput;
         use bevy::input::keyboard::KeyCode;
-        use petitset::PetitSet;
 
         let mut input_map_1 = InputMap::<Action>::default();
         input_map_1.insert(KeyCode::Space, Action::Run);
@@ -606,7 +1671,7 @@ mod tests {
 
         assert_eq!(
             *input_map_1.get(Action::Run),
-            PetitSet::<UserInput, 16>::from_iter([KeyCode::Space.into(), KeyCode::Return.into()])
+            InputSet::<UserInput, 16>::from_iter([KeyCode::Space.into(), KeyCode::Return.into()])
         );
 
         let input_map_2 = InputMap::<Action>::new([
@@ -617,6 +1682,28 @@ mod tests {
         assert_eq!(input_map_1, input_map_2);
     }
 
+    #[test]
+    fn unbounded_bindings() {
+        use crate::user_input::InputKind;
+        use bevy::input::keyboard::KeyCode::*;
+
+        let keys = [
+            A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        ];
+
+        let mut input_map = InputMap::<Action>::default();
+        input_map.insert_multiple(keys.iter().map(|&key| (key, Action::Run)));
+        assert_eq!(input_map.get(Action::Run).len(), keys.len());
+
+        input_map.insert_chord(keys, Action::Jump);
+        let chord = input_map.get(Action::Jump).get_at(0).unwrap();
+        assert_eq!(chord.len(), keys.len());
+        assert_eq!(
+            chord.n_matching(&keys.iter().map(|&key| InputKind::from(key)).collect()),
+            keys.len()
+        );
+    }
+
     #[test]
     fn chord_singleton_coercion() {
         use crate::input_map::UserInput;
@@ -658,6 +1745,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn settings_of_unbound_inputs() {
+        use crate::axis_filters::AxisFilter;
+        use crate::user_input::TriggerMode;
+        use bevy::input::keyboard::KeyCode;
+        use bevy::utils::Duration;
+
+        let mut input_map = InputMap::new([(KeyCode::Space, Action::Jump)]);
+        input_map
+            .set_trigger(Action::Jump, KeyCode::W, TriggerMode::Release)
+            .set_filters(
+                Action::Jump,
+                KeyCode::W,
+                [AxisFilter::MaxRate {
+                    time_per_unit: Duration::from_millis(100),
+                }],
+            );
+        assert_eq!(input_map, InputMap::new([(KeyCode::Space, Action::Jump)]));
+
+        // Binding the input later does not apply its settings
+        input_map.insert(KeyCode::W, Action::Jump);
+        assert_eq!(
+            input_map.trigger(Action::Jump, &KeyCode::W.into()),
+            TriggerMode::Press
+        );
+        assert!(input_map
+            .filters(Action::Jump, &KeyCode::W.into())
+            .is_empty());
+    }
+
     #[test]
     fn merging() {
         use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode};
@@ -679,6 +1796,342 @@ mod tests {
         assert_eq!(input_map, default_keyboard_map);
     }
 
+    #[test]
+    fn diffing() {
+        use bevy::input::gamepad::Gamepad;
+        use bevy::input::keyboard::KeyCode;
+
+        let defaults = InputMap::new([
+            (KeyCode::LShift, Action::Run),
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::H, Action::Hide),
+        ]);
+        assert!(defaults.diff(&defaults).is_empty());
+
+        let mut customized = defaults.clone();
+        customized.remove(Action::Hide, KeyCode::H);
+        customized.insert(KeyCode::C, Action::Hide);
+        customized.insert(KeyCode::W, Action::Jump);
+
+        let diff = defaults.diff(&customized);
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(diff.removed.len(), 1);
+
+        // Reapplying the diff recreates the customized map
+        let mut reapplied = defaults.clone();
+        reapplied.apply_diff(&diff);
+        assert_eq!(reapplied, customized);
+
+        // Diffs are idempotent
+        reapplied.apply_diff(&diff);
+        assert_eq!(reapplied, customized);
+
+        // Diffs can be applied on top of newer defaults, without changing the gamepad
+        let gamepad = Gamepad { id: 0 };
+        let mut new_defaults = InputMap::new([
+            (KeyCode::LControl, Action::Run),
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::H, Action::Hide),
+        ]);
+        new_defaults.set_gamepad(gamepad).apply_diff(&diff);
+
+        let mut expected = InputMap::new([
+            (KeyCode::LControl, Action::Run),
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::W, Action::Jump),
+            (KeyCode::C, Action::Hide),
+        ]);
+        expected.set_gamepad(gamepad);
+        assert_eq!(new_defaults, expected);
+    }
+
+    #[test]
+    fn diff_serde() {
+        use bevy::input::keyboard::KeyCode;
+        use serde_test::{assert_tokens, Token};
+
+        let defaults = InputMap::new([
+            (KeyCode::LShift, Action::Run),
+            (KeyCode::Space, Action::Jump),
+        ]);
+        let mut customized = defaults.clone();
+        customized.remove(Action::Jump, KeyCode::Space);
+        customized.insert(KeyCode::W, Action::Jump);
+        let diff = defaults.diff(&customized);
+
+        // Each diff is an input map with a single `Jump` binding, and no bindings for the other actions
+        let binding_tokens = |key: &'static str| {
+            let action_tokens = |variant: &'static str| Token::UnitVariant {
+                name: "Action",
+                variant,
+            };
+
+            [
+                Token::Struct {
+                    name: "InputMap",
+                    len: 6,
+                },
+                Token::Str("map"),
+                Token::Map { len: Some(3) },
+                action_tokens("Run"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                action_tokens("Jump"),
+                Token::Seq { len: Some(1) },
+                Token::NewtypeVariant {
+                    name: "UserInput",
+                    variant: "Single",
+                },
+                Token::NewtypeVariant {
+                    name: "InputKind",
+                    variant: "Keyboard",
+                },
+                Token::UnitVariant {
+                    name: "KeyCode",
+                    variant: key,
+                },
+                Token::SeqEnd,
+                action_tokens("Hide"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::MapEnd,
+            ]
+            .into_iter()
+            .chain(default_settings_tokens())
+            .chain([Token::StructEnd])
+        };
+
+        let mut tokens = vec![
+            Token::Struct {
+                name: "InputMapDiff",
+                len: 3,
+            },
+            Token::Str("added"),
+        ];
+        tokens.extend(binding_tokens("W"));
+        tokens.push(Token::Str("removed"));
+        tokens.extend(binding_tokens("Space"));
+        tokens.extend([
+            Token::Str("settings"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+        ]);
+        assert_tokens(&diff, &tokens);
+
+        // Deserialized diffs can be reapplied
+        let serialized = ron::to_string(&diff).unwrap();
+        let deserialized: super::InputMapDiff<Action> = ron::from_str(&serialized).unwrap();
+        let mut reapplied = defaults.clone();
+        reapplied.apply_diff(&deserialized);
+        assert_eq!(reapplied, customized);
+    }
+
+    #[test]
+    fn diffing_settings() {
+        use crate::axis_filters::AxisFilter;
+        use crate::axislike::MouseMotionNormalization;
+        use crate::user_input::{AggregationPolicy, ChordTiming, TriggerMode};
+        use bevy::input::keyboard::KeyCode;
+        use bevy::utils::Duration;
+
+        let mut defaults = InputMap::new([
+            (KeyCode::LShift, Action::Run),
+            (KeyCode::Space, Action::Jump),
+        ]);
+        defaults.set_trigger(Action::Jump, KeyCode::Space, TriggerMode::Release);
+
+        let mut customized = defaults.clone();
+        customized
+            .set_trigger(Action::Jump, KeyCode::Space, TriggerMode::Press)
+            .insert_with_trigger(
+                KeyCode::H,
+                Action::Hide,
+                TriggerMode::Click {
+                    max_duration: Duration::from_millis(200),
+                },
+            )
+            .set_filters(
+                Action::Run,
+                KeyCode::LShift,
+                [AxisFilter::Acceleration {
+                    ramp: Duration::from_millis(300),
+                }],
+            )
+            .set_chord_timing(Action::Hide, ChordTiming::default())
+            .set_aggregation(Action::Run, AggregationPolicy::MaxMagnitude)
+            .set_mouse_motion_normalization(MouseMotionNormalization::Rate { scale: 0.01 });
+
+        let diff = defaults.diff(&customized);
+        assert_eq!(diff.settings.len(), 6);
+
+        let mut reapplied = defaults.clone();
+        reapplied.apply_diff(&diff);
+        assert_eq!(reapplied, customized);
+
+        // Settings survive serialization
+        let serialized = ron::to_string(&diff).unwrap();
+        let deserialized: super::InputMapDiff<Action> = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, diff);
+
+        // Changes to bindings that no longer exist are skipped
+        let mut new_defaults = InputMap::new([(KeyCode::LControl, Action::Run)]);
+        new_defaults.apply_diff(&diff);
+        assert!(new_defaults
+            .filters(Action::Run, &KeyCode::LShift.into())
+            .is_empty());
+        assert_eq!(
+            new_defaults.aggregation(Action::Run),
+            AggregationPolicy::MaxMagnitude
+        );
+    }
+
+    #[test]
+    fn fallible_insertion() {
+        use crate::axislike::SingleAxis;
+        use crate::errors::InputMapError;
+        use bevy::input::gamepad::GamepadAxisType;
+        use bevy::input::keyboard::KeyCode;
+
+        let mut input_map = InputMap::<Action>::default();
+        assert!(input_map.try_insert(KeyCode::Space, Action::Run).is_ok());
+        assert_eq!(
+            input_map
+                .try_insert(KeyCode::Space, Action::Run)
+                .unwrap_err(),
+            InputMapError::DuplicateBinding {
+                input: KeyCode::Space.into()
+            }
+        );
+
+        // The same input can still be bound to other actions
+        assert!(input_map.try_insert(KeyCode::Space, Action::Jump).is_ok());
+
+        let inverted_axis = SingleAxis::symmetric(GamepadAxisType::LeftZ, -0.5);
+        assert_eq!(
+            input_map
+                .try_insert(inverted_axis, Action::Hide)
+                .unwrap_err(),
+            InputMapError::InvalidAxisThreshold {
+                axis: inverted_axis
+            }
+        );
+        assert!(input_map.get(Action::Hide).is_empty());
+    }
+
+    #[test]
+    fn fallible_merging() {
+        use crate::axislike::SingleAxis;
+        use crate::errors::InputMapError;
+        use bevy::input::gamepad::GamepadAxisType;
+        use bevy::input::keyboard::KeyCode;
+
+        let mut input_map = InputMap::<Action>::new([(KeyCode::Space, Action::Jump)]);
+        let mut other = InputMap::new([(KeyCode::Space, Action::Jump), (KeyCode::R, Action::Run)]);
+
+        // Shared bindings are not treated as errors
+        input_map.try_merge(&other).unwrap();
+        assert_eq!(input_map, other);
+
+        // Merging is all-or-nothing
+        let inverted_axis = SingleAxis::symmetric(GamepadAxisType::LeftZ, -0.5);
+        other.insert(KeyCode::H, Action::Hide);
+        other.insert(inverted_axis, Action::Hide);
+        assert_eq!(
+            input_map.try_merge(&other).unwrap_err(),
+            InputMapError::InvalidAxisThreshold {
+                axis: inverted_axis
+            }
+        );
+        assert!(input_map.get(Action::Hide).is_empty());
+    }
+
+    #[test]
+    fn kind_validation() {
+        use crate::axislike::{DualAxis, SingleAxis, VirtualDPad};
+        use crate::errors::InputMapError;
+        use crate::user_input::InputKind;
+        use bevy::input::gamepad::GamepadAxisType;
+        use bevy::input::keyboard::KeyCode;
+
+        #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        enum KindedAction {
+            #[actionlike(kind = "Button")]
+            Jump,
+            #[actionlike(kind = "Axis")]
+            Throttle,
+            #[actionlike(kind = "DualAxis")]
+            Move,
+        }
+
+        let mut input_map = InputMap::default();
+        input_map
+            .insert(KeyCode::Space, KindedAction::Jump)
+            .insert(
+                SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1),
+                KindedAction::Jump,
+            )
+            .insert(
+                SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1),
+                KindedAction::Throttle,
+            )
+            .insert(KeyCode::W, KindedAction::Throttle)
+            .insert(DualAxis::left_stick(), KindedAction::Move)
+            .insert(VirtualDPad::arrow_keys(), KindedAction::Move)
+            .insert_chord(
+                [
+                    InputKind::from(KeyCode::LShift),
+                    DualAxis::left_stick().into(),
+                ],
+                KindedAction::Move,
+            );
+
+        assert_eq!(
+            input_map
+                .try_insert(DualAxis::left_stick(), KindedAction::Jump)
+                .unwrap_err(),
+            InputMapError::IncompatibleBinding {
+                input: DualAxis::left_stick().into(),
+                kind: ActionKind::Button,
+            }
+        );
+        assert!(input_map
+            .try_insert(VirtualDPad::wasd(), KindedAction::Throttle)
+            .is_err());
+        assert!(input_map
+            .try_insert(KeyCode::Space, KindedAction::Move)
+            .is_err());
+
+        // Actions without a declared kind accept any input
+        assert!(InputMap::default()
+            .try_insert(DualAxis::left_stick(), Action::Jump)
+            .is_ok());
+
+        let result = std::panic::catch_unwind(|| {
+            let _ = InputMap::new([(DualAxis::left_stick(), KindedAction::Jump)]);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unknown_gamepad() {
+        use crate::errors::InputMapError;
+        use bevy::input::gamepad::{Gamepad, Gamepads};
+
+        let mut input_map = InputMap::<Action>::default();
+        let gamepads = Gamepads::default();
+
+        assert_eq!(
+            input_map
+                .try_set_gamepad(Gamepad { id: 0 }, &gamepads)
+                .unwrap_err(),
+            InputMapError::UnknownGamepad {
+                gamepad: Gamepad { id: 0 }
+            }
+        );
+        assert_eq!(input_map.gamepad(), None);
+    }
+
     #[test]
     fn gamepad_swapping() {
         use bevy::input::gamepad::Gamepad;
@@ -730,102 +2183,181 @@ mod tests {
         input_map.insert(KeyCode::LShift, Action::Run);
         input_map.insert(KeyCode::RShift, Action::Run);
 
-        assert_tokens(
-            &input_map,
-            &[
-                Token::Struct {
-                    name: "InputMap",
-                    len: 1,
-                },
-                Token::Str("map"),
-                Token::Map { len: Some(3) },
-                Token::UnitVariant {
-                    name: "Action",
-                    variant: "Run",
-                },
-                Token::Seq { len: Some(2) },
-                Token::NewtypeVariant {
-                    name: "UserInput",
-                    variant: "Single",
-                },
-                Token::NewtypeVariant {
-                    name: "InputKind",
-                    variant: "Keyboard",
-                },
-                Token::UnitVariant {
-                    name: "KeyCode",
-                    variant: "LShift",
-                },
-                Token::NewtypeVariant {
-                    name: "UserInput",
-                    variant: "Single",
-                },
-                Token::NewtypeVariant {
-                    name: "InputKind",
-                    variant: "Keyboard",
-                },
-                Token::UnitVariant {
-                    name: "KeyCode",
-                    variant: "RShift",
-                },
-                Token::SeqEnd,
-                Token::UnitVariant {
-                    name: "Action",
-                    variant: "Jump",
-                },
-                Token::Seq { len: Some(1) },
-                Token::NewtypeVariant {
-                    name: "UserInput",
-                    variant: "Single",
-                },
-                Token::NewtypeVariant {
-                    name: "InputKind",
-                    variant: "Keyboard",
-                },
-                Token::UnitVariant {
-                    name: "KeyCode",
-                    variant: "Space",
-                },
-                Token::SeqEnd,
-                Token::UnitVariant {
-                    name: "Action",
-                    variant: "Hide",
-                },
-                Token::Seq { len: Some(1) },
-                Token::NewtypeVariant {
-                    name: "UserInput",
-                    variant: "Chord",
-                },
-                Token::Seq { len: Some(8) },
-                Token::Some,
-                Token::NewtypeVariant {
-                    name: "InputKind",
-                    variant: "Keyboard",
-                },
-                Token::UnitVariant {
-                    name: "KeyCode",
-                    variant: "R",
-                },
-                Token::Some,
-                Token::NewtypeVariant {
-                    name: "InputKind",
-                    variant: "Keyboard",
-                },
-                Token::UnitVariant {
-                    name: "KeyCode",
-                    variant: "E",
+        let mut tokens = vec![
+            Token::Struct {
+                name: "InputMap",
+                len: 6,
+            },
+            Token::Str("map"),
+            Token::Map { len: Some(3) },
+            Token::UnitVariant {
+                name: "Action",
+                variant: "Run",
+            },
+            Token::Seq { len: Some(2) },
+            Token::NewtypeVariant {
+                name: "UserInput",
+                variant: "Single",
+            },
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant: "LShift",
+            },
+            Token::NewtypeVariant {
+                name: "UserInput",
+                variant: "Single",
+            },
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant: "RShift",
+            },
+            Token::SeqEnd,
+            Token::UnitVariant {
+                name: "Action",
+                variant: "Jump",
+            },
+            Token::Seq { len: Some(1) },
+            Token::NewtypeVariant {
+                name: "UserInput",
+                variant: "Single",
+            },
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant: "Space",
+            },
+            Token::SeqEnd,
+            Token::UnitVariant {
+                name: "Action",
+                variant: "Hide",
+            },
+            Token::Seq { len: Some(1) },
+            Token::NewtypeVariant {
+                name: "UserInput",
+                variant: "Chord",
+            },
+            Token::Seq { len: Some(8) },
+            Token::Some,
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant: "R",
+            },
+            Token::Some,
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant: "E",
+            },
+            Token::None,
+            Token::None,
+            Token::None,
+            Token::None,
+            Token::None,
+            Token::None,
+            Token::SeqEnd,
+            Token::SeqEnd,
+            Token::MapEnd,
+        ];
+        tokens.extend(default_settings_tokens());
+        tokens.push(Token::StructEnd);
+        assert_tokens(&input_map, &tokens);
+    }
+
+    #[test]
+    fn serde_settings() {
+        use crate::axis_filters::AxisFilter;
+        use crate::axislike::MouseMotionNormalization;
+        use crate::user_input::{AggregationPolicy, ChordTiming, TriggerMode};
+        use bevy::input::keyboard::KeyCode;
+        use bevy::utils::Duration;
+
+        let mut input_map = InputMap::new([
+            (KeyCode::LShift, Action::Run),
+            (KeyCode::Space, Action::Jump),
+        ]);
+        input_map
+            .insert_chord([KeyCode::LControl, KeyCode::H], Action::Hide)
+            .set_chord_timing(
+                Action::Hide,
+                ChordTiming {
+                    window: Some(Duration::from_millis(150)),
+                    ordered: true,
                 },
-                Token::None,
-                Token::None,
-                Token::None,
-                Token::None,
-                Token::None,
-                Token::None,
-                Token::SeqEnd,
-                Token::SeqEnd,
-                Token::MapEnd,
-                Token::StructEnd,
-            ],
+            )
+            .set_aggregation(Action::Run, AggregationPolicy::FirstBinding)
+            .set_trigger(Action::Jump, KeyCode::Space, TriggerMode::Release)
+            .set_filters(
+                Action::Run,
+                KeyCode::LShift,
+                [AxisFilter::MaxRate {
+                    time_per_unit: Duration::from_millis(100),
+                }],
+            )
+            .set_mouse_motion_normalization(MouseMotionNormalization::Stick { scale: 0.002 });
+
+        let serialized = ron::to_string(&input_map).unwrap();
+        let deserialized: InputMap<Action> = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, input_map);
+
+        // Settings are optional, and settings of bindings that do not exist are skipped
+        let deserialized: InputMap<Action> = ron::from_str(
+            "(map: {Jump: [Single(Keyboard(Space))]}, triggers: {Jump: [(Single(Keyboard(W)), Release)]}, aggregation: {Jump: MaxMagnitude})",
         )
+        .unwrap();
+        assert_eq!(
+            deserialized.trigger(Action::Jump, &KeyCode::W.into()),
+            TriggerMode::Press
+        );
+        assert_eq!(
+            deserialized.aggregation(Action::Jump),
+            AggregationPolicy::MaxMagnitude
+        );
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        use crate::user_input::{AggregationPolicy, TriggerMode};
+        use bevy::input::keyboard::KeyCode;
+
+        // Formats that are not self-describing read every field by position
+        let defaults = InputMap::new([
+            (KeyCode::LShift, Action::Run),
+            (KeyCode::Space, Action::Jump),
+        ]);
+        let serialized = bincode::serialize(&defaults).unwrap();
+        let deserialized: InputMap<Action> = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, defaults);
+
+        let mut customized = defaults.clone();
+        customized
+            .insert_chord([KeyCode::LControl, KeyCode::H], Action::Hide)
+            .set_aggregation(Action::Run, AggregationPolicy::FirstBinding)
+            .set_trigger(Action::Jump, KeyCode::Space, TriggerMode::Release);
+        let serialized = bincode::serialize(&customized).unwrap();
+        let deserialized: InputMap<Action> = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, customized);
+
+        let diff = defaults.diff(&customized);
+        let serialized = bincode::serialize(&diff).unwrap();
+        let deserialized: super::InputMapDiff<Action> = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized, diff);
     }
 }
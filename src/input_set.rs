@@ -0,0 +1,427 @@
+//! Contains [`InputSet`], the growable storage used for input bindings and chords.
+
+use derive_more::{Display, Error};
+use serde::de::{SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use smallvec::SmallVec;
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+/// A set-like collection of inputs with stable, index-addressable slots
+///
+/// The first `INLINE` slots are stored inline, without any heap allocation.
+/// This keeps the common case of a handful of bindings per action (or buttons per chord) as fast
+/// as a fixed-size array: iteration, insertion and look-up are all simple linear scans.
+/// Once more than `INLINE` slots are needed, the storage spills onto the heap,
+/// so there is no upper bound on the number of elements that can be stored.
+///
+/// Like the fixed-capacity set this replaces, removing an element leaves an empty slot behind,
+/// so the indexes of the remaining elements do not change.
+/// Empty slots are reused by later insertions.
+///
+/// Equality is set equality: two [`InputSet`]s are equal if they contain the same elements,
+/// regardless of order, slot positions or inline capacity.
+#[derive(Clone)]
+pub struct InputSet<T, const INLINE: usize> {
+    slots: SmallVec<[Option<T>; INLINE]>,
+}
+
+impl<T, const INLINE: usize> Default for InputSet<T, INLINE> {
+    fn default() -> Self {
+        InputSet {
+            slots: SmallVec::new(),
+        }
+    }
+}
+
+impl<T, const INLINE: usize> InputSet<T, INLINE> {
+    /// Creates a new, empty [`InputSet`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of elements in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Are there exactly 0 elements in the set?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(|slot| slot.is_none())
+    }
+
+    /// Has the set spilled its elements onto the heap?
+    ///
+    /// This is `false` as long as no more than `INLINE` slots have been used.
+    #[must_use]
+    pub fn spilled(&self) -> bool {
+        self.slots.spilled()
+    }
+
+    /// Returns an iterator over the elements of the set, in slot order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().flatten()
+    }
+
+    /// Returns a reference to the element stored in the provided slot
+    ///
+    /// Returns [`None`] if the index is out of bounds or the slot is empty.
+    #[must_use]
+    pub fn get_at(&self, index: usize) -> Option<&T> {
+        self.slots.get(index).and_then(Option::as_ref)
+    }
+
+    /// Removes all elements from the set
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Removes the element at the provided index
+    ///
+    /// Returns `true` if an element was found.
+    pub fn remove_at(&mut self, index: usize) -> bool {
+        self.take_at(index).is_some()
+    }
+
+    /// Removes and returns the element at the provided index, if any
+    pub fn take_at(&mut self, index: usize) -> Option<T> {
+        let taken = self.slots.get_mut(index).and_then(Option::take);
+        self.trim();
+        taken
+    }
+
+    /// Drops any trailing empty slots, so that the storage never grows due to removals
+    fn trim(&mut self) {
+        while let Some(None) = self.slots.last() {
+            self.slots.pop();
+        }
+    }
+
+    /// Makes sure that the slot at `index` exists, allocating empty slots as needed
+    fn try_grow_to(&mut self, index: usize) -> Result<(), smallvec::CollectionAllocErr> {
+        if index >= self.slots.len() {
+            let additional = index + 1 - self.slots.len();
+            self.slots.try_reserve(additional)?;
+            self.slots
+                .extend(std::iter::repeat_with(|| None).take(additional));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: PartialEq, const INLINE: usize> InputSet<T, INLINE> {
+    /// Returns the index of the provided element, if it is in the set
+    #[must_use]
+    pub fn find(&self, element: &T) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|slot| slot.as_ref() == Some(element))
+    }
+
+    /// Is the provided element in the set?
+    #[must_use]
+    pub fn contains(&self, element: &T) -> bool {
+        self.find(element).is_some()
+    }
+
+    /// Is every element of this set also contained in `other`?
+    #[must_use]
+    pub fn is_subset<const OTHER_INLINE: usize>(&self, other: &InputSet<T, OTHER_INLINE>) -> bool {
+        self.iter().all(|element| other.contains(element))
+    }
+
+    /// Attempts to insert the element into the first empty slot of the set
+    ///
+    /// Duplicate elements are not inserted again:
+    /// the returned [`SuccessfulInsertion`] reports where the element is stored, and whether it is new.
+    ///
+    /// The set is unbounded, so this can only fail if the storage could not grow.
+    /// In that case, the rejected element is returned inside of the [`CapacityError`].
+    pub fn try_insert(&mut self, element: T) -> Result<SuccessfulInsertion, CapacityError<T>> {
+        if let Some(index) = self.find(&element) {
+            return Ok(SuccessfulInsertion::Extant(index));
+        }
+
+        let index = self
+            .slots
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or(self.slots.len());
+
+        if self.try_grow_to(index).is_err() {
+            return Err(CapacityError(element));
+        }
+
+        self.slots[index] = Some(element);
+        Ok(SuccessfulInsertion::Novel(index))
+    }
+
+    /// Inserts the element into the first empty slot of the set
+    ///
+    /// Duplicate elements are not inserted again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage could not grow to fit the new element.
+    pub fn insert(&mut self, element: T) -> SuccessfulInsertion {
+        self.try_insert(element)
+            .unwrap_or_else(|_| panic!("Could not allocate space for a new element of the set!"))
+    }
+
+    /// Attempts to insert the element at the provided index
+    ///
+    /// If the element was already in the set, it will be moved to the supplied index,
+    /// and any element that was previously there will be moved to the element's original index.
+    /// Otherwise, any element stored at `index` is replaced, and returned.
+    ///
+    /// Indexes past the end of the set are valid: the set grows to fit them.
+    pub fn try_insert_at(
+        &mut self,
+        element: T,
+        index: usize,
+    ) -> Result<Option<T>, CapacityError<T>> {
+        if self.try_grow_to(index).is_err() {
+            return Err(CapacityError(element));
+        }
+
+        let replaced = if let Some(old_index) = self.find(&element) {
+            self.slots.swap(old_index, index);
+            None
+        } else {
+            self.slots[index].replace(element)
+        };
+
+        self.trim();
+        Ok(replaced)
+    }
+
+    /// Inserts the element at the provided index
+    ///
+    /// See [`InputSet::try_insert_at`] for the exact semantics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage could not grow to fit the provided index.
+    pub fn insert_at(&mut self, element: T, index: usize) -> Option<T> {
+        self.try_insert_at(element, index)
+            .unwrap_or_else(|_| panic!("Could not allocate space for a new element of the set!"))
+    }
+
+    /// Removes the element from the set, if it exists
+    ///
+    /// Returns `Some(index)` if the element was found, or `None` if no matching element was found.
+    pub fn remove(&mut self, element: &T) -> Option<usize> {
+        let index = self.find(element)?;
+        self.take_at(index);
+        Some(index)
+    }
+}
+
+impl<T: PartialEq, const INLINE: usize> FromIterator<T> for InputSet<T, INLINE> {
+    /// Collects the elements in order, discarding duplicates
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = InputSet::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PartialEq, const INLINE: usize> Extend<T> for InputSet<T, INLINE> {
+    /// Inserts each element in order, discarding duplicates
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.insert(element);
+        }
+    }
+}
+
+impl<T, const INLINE: usize> IntoIterator for InputSet<T, INLINE> {
+    type Item = T;
+    type IntoIter = std::iter::Flatten<smallvec::IntoIter<[Option<T>; INLINE]>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slots.into_iter().flatten()
+    }
+}
+
+impl<T: Debug, const INLINE: usize> Debug for InputSet<T, INLINE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const INLINE: usize, const OTHER_INLINE: usize>
+    PartialEq<InputSet<T, OTHER_INLINE>> for InputSet<T, INLINE>
+{
+    /// Tests set-equality between the two sets
+    ///
+    /// This is independent of order, slot positions and inline capacity.
+    fn eq(&self, other: &InputSet<T, OTHER_INLINE>) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<T: Eq, const INLINE: usize> Eq for InputSet<T, INLINE> {}
+
+impl<T: Hash, const INLINE: usize> Hash for InputSet<T, INLINE> {
+    /// Hashes the set in an order-independent way, to agree with its set-equality
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut combined: u64 = 0;
+        let mut len: usize = 0;
+        for element in self.iter() {
+            let mut hasher = DefaultHasher::new();
+            element.hash(&mut hasher);
+            combined = combined.wrapping_add(hasher.finish());
+            len += 1;
+        }
+
+        len.hash(state);
+        combined.hash(state);
+    }
+}
+
+impl<T: Serialize, const INLINE: usize> Serialize for InputSet<T, INLINE> {
+    /// Serializes the slots as a sequence of `Option<T>`, padded to at least `INLINE` entries
+    ///
+    /// This matches the format used by the fixed-capacity sets that this type replaces,
+    /// so existing files remain readable (and writable) in both directions as long as they fit inline.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = self.slots.len().max(INLINE);
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for slot in self.slots.iter() {
+            seq.serialize_element(slot)?;
+        }
+        for _ in self.slots.len()..len {
+            seq.serialize_element(&None::<T>)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de> + PartialEq, const INLINE: usize> Deserialize<'de>
+    for InputSet<T, INLINE>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InputSetVisitor<T, const INLINE: usize> {
+            marker: PhantomData<fn() -> InputSet<T, INLINE>>,
+        }
+
+        impl<'de, T: Deserialize<'de> + PartialEq, const INLINE: usize> Visitor<'de>
+            for InputSetVisitor<T, INLINE>
+        {
+            type Value = InputSet<T, INLINE>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence of `Option<T>` slots")
+            }
+
+            fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+                let mut set = InputSet::default();
+                let mut index = 0;
+                while let Some(slot) = seq.next_element::<Option<T>>()? {
+                    if let Some(element) = slot {
+                        // Duplicates can only be found in hand-written files: keep the first copy
+                        if !set.contains(&element) {
+                            set.insert_at(element, index);
+                        }
+                    }
+                    index += 1;
+                }
+
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(InputSetVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+/// The `Ok` result of a successful [`InputSet`] insertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuccessfulInsertion {
+    /// This is a new element: it is now stored at the provided index
+    Novel(usize),
+    /// This element was already in the set: it is stored at the provided index
+    Extant(usize),
+}
+
+/// The [`InputSet`] could not allocate enough space to store the contained element
+///
+/// [`InputSet`]s have no maximum size, so this only occurs when memory allocation fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Error)]
+#[display(fmt = "could not allocate space for the new element")]
+pub struct CapacityError<T>(#[error(not(source))] pub T);
+
+#[cfg(test)]
+mod tests {
+    use super::InputSet;
+
+    #[test]
+    fn spills_past_inline_capacity() {
+        let mut set = InputSet::<usize, 2>::default();
+        set.extend(0..100);
+
+        assert!(set.spilled());
+        assert_eq!(set.len(), 100);
+        assert!(set.iter().copied().eq(0..100));
+    }
+
+    #[test]
+    fn removal_keeps_indexes_stable() {
+        let mut set = InputSet::<char, 4>::from_iter(['a', 'b', 'c']);
+
+        assert!(set.remove_at(1));
+        assert!(!set.remove_at(1));
+        assert_eq!(set.get_at(2), Some(&'c'));
+
+        // Empty slots are reused first
+        set.insert('d');
+        assert_eq!(set.get_at(1), Some(&'d'));
+    }
+
+    #[test]
+    fn insert_at_grows_and_swaps() {
+        let mut set = InputSet::<char, 2>::default();
+        assert_eq!(set.insert_at('a', 5), None);
+        assert_eq!(set.get_at(5), Some(&'a'));
+        assert_eq!(set.len(), 1);
+
+        set.insert_at('b', 0);
+        // Inserting an existing element moves it, swapping with the current occupant
+        set.insert_at('a', 0);
+        assert_eq!(set.get_at(0), Some(&'a'));
+        assert_eq!(set.get_at(5), Some(&'b'));
+
+        // Replacing an occupied slot returns the old element
+        assert_eq!(set.insert_at('c', 0), Some('a'));
+    }
+
+    #[test]
+    fn set_equality_and_hashing() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let a = InputSet::<u8, 2>::from_iter([1, 2, 3]);
+        let b = InputSet::<u8, 8>::from_iter([3, 1, 2]);
+        assert_eq!(a, b);
+
+        fn hash_of(set: &InputSet<u8, 2>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            set.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let c = InputSet::<u8, 2>::from_iter([2, 3, 1]);
+        assert_eq!(hash_of(&a), hash_of(&c));
+    }
+}
@@ -1,22 +1,24 @@
 //! Unified input streams for working with [`bevy::input`] data.
 
 use bevy::input::{
-    gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadEventRaw, Gamepads},
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadEventRaw, Gamepads},
     keyboard::{KeyCode, KeyboardInput},
     mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
     Axis, Input,
 };
-use petitset::PetitSet;
 
-use bevy::ecs::prelude::{Events, ResMut, World};
+use bevy::ecs::prelude::{Events, ResMut, Resource, World};
 use bevy::ecs::system::SystemState;
+use bevy::time::Time;
+use bevy::utils::{Duration, HashMap, HashSet, Instant};
 
 use crate::axislike::{
-    AxisType, DualAxisData, MouseMotionAxisType, MouseWheelAxisType, SingleAxis, VirtualAxis,
-    VirtualDPad,
+    AxisType, DualAxis, DualAxisData, MouseMotionAxisType, MouseMotionNormalization,
+    MouseWheelAxisType, SingleAxis, VirtualAxis, VirtualDPad,
 };
 use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
-use crate::user_input::{InputKind, UserInput};
+use crate::input_set::InputSet;
+use crate::user_input::{ChordTiming, InputDevice, InputKind, UserInput};
 
 /// A collection of [`Input`] structs, which can be used to update an [`InputMap`](crate::input_map::InputMap).
 ///
@@ -41,11 +43,23 @@ pub struct InputStreams<'a> {
     pub mouse_motion: &'a Events<MouseMotion>,
     /// The [`Gamepad`] that this struct will detect inputs from
     pub associated_gamepad: Option<Gamepad>,
+    /// When each key and button was last pressed, used to check [`ChordTiming`] constraints
+    /// and to find the most recently used [`InputDevice`]
+    ///
+    /// If this is [`None`], [`ChordTiming`] constraints are ignored, and no device is considered to have been used.
+    pub press_times: Option<&'a ButtonPressTimes>,
+    /// How the [`MouseMotion`] of each frame is converted into axis values
+    pub mouse_motion_normalization: MouseMotionNormalization,
+    /// How long the current frame lasted, used to normalize [`MouseMotion`]
+    pub frame_time: Duration,
 }
 
 // Constructors
 impl<'a> InputStreams<'a> {
     /// Construct an [`InputStreams`] from a [`World`]
+    ///
+    /// The frame time is read from the [`Time`] resource, if any,
+    /// and [`MouseMotion`] is not normalized.
     pub fn from_world(world: &'a World, gamepad: Option<Gamepad>) -> Self {
         let gamepad_buttons = world.resource::<Input<GamepadButton>>();
         let gamepad_button_axes = world.resource::<Axis<GamepadButton>>();
@@ -55,6 +69,11 @@ impl<'a> InputStreams<'a> {
         let mouse_buttons = world.get_resource::<Input<MouseButton>>();
         let mouse_wheel = world.get_resource::<Events<MouseWheel>>();
         let mouse_motion = world.resource::<Events<MouseMotion>>();
+        let press_times = world.get_resource::<ButtonPressTimes>();
+        let frame_time = world
+            .get_resource::<Time>()
+            .map(|time| time.delta())
+            .unwrap_or_default();
 
         InputStreams {
             gamepad_buttons,
@@ -66,6 +85,9 @@ impl<'a> InputStreams<'a> {
             mouse_wheel,
             mouse_motion,
             associated_gamepad: gamepad,
+            press_times,
+            mouse_motion_normalization: MouseMotionNormalization::Raw,
+            frame_time,
         }
     }
 }
@@ -109,7 +131,7 @@ impl<'a> InputStreams<'a> {
 
     /// Is at least one of the `inputs` pressed?
     #[must_use]
-    pub fn any_pressed(&self, inputs: &PetitSet<UserInput, 16>) -> bool {
+    pub fn any_pressed(&self, inputs: &InputSet<UserInput, 16>) -> bool {
         for input in inputs.iter() {
             if self.input_pressed(input) {
                 return true;
@@ -217,7 +239,7 @@ impl<'a> InputStreams<'a> {
 
     /// Are all of the `buttons` pressed?
     #[must_use]
-    pub fn all_buttons_pressed(&self, buttons: &PetitSet<InputKind, 8>) -> bool {
+    pub fn all_buttons_pressed(&self, buttons: &InputSet<InputKind, 8>) -> bool {
         for &button in buttons.iter() {
             // If any of the appropriate inputs failed to match, the action is considered pressed
             if !self.button_pressed(button) {
@@ -228,6 +250,77 @@ impl<'a> InputStreams<'a> {
         true
     }
 
+    /// Were the `buttons` of a chord pressed at times that satisfy the `timing` constraints?
+    ///
+    /// Only the press times of keys and buttons are known, so other inputs are not constrained.
+    /// Always returns `true` if [`InputStreams::press_times`] is [`None`].
+    #[must_use]
+    pub fn chord_timing_satisfied(
+        &self,
+        buttons: &InputSet<InputKind, 8>,
+        timing: ChordTiming,
+    ) -> bool {
+        let Some(press_times) = self.press_times else {
+            return true;
+        };
+
+        let instants: Vec<Instant> = buttons
+            .iter()
+            .filter_map(|&button| self.press_instant(press_times, button))
+            .collect();
+
+        if timing.ordered && instants.windows(2).any(|pair| pair[0] > pair[1]) {
+            return false;
+        }
+
+        match (timing.window, instants.iter().min(), instants.iter().max()) {
+            (Some(window), Some(&first), Some(&last)) => last - first <= window,
+            _ => true,
+        }
+    }
+
+    /// When was an [`InputDevice`] that the `input` comes from last used?
+    ///
+    /// Gamepads other than the [associated gamepad](InputStreams::associated_gamepad) are ignored, if one is set.
+    /// Returns [`None`] if [`InputStreams::press_times`] is [`None`], or none of the devices have been used.
+    #[must_use]
+    pub fn device_last_used(&self, input: &UserInput) -> Option<Instant> {
+        let press_times = self.press_times?;
+        input
+            .devices()
+            .into_iter()
+            .filter_map(|device| press_times.last_used(device, self.associated_gamepad))
+            .max()
+    }
+
+    /// When was the `button` pressed, if it is a key or button whose press time is known?
+    fn press_instant(&self, press_times: &ButtonPressTimes, button: InputKind) -> Option<Instant> {
+        match button {
+            InputKind::Keyboard(keycode) => press_times.keycodes.get(&keycode).copied(),
+            // Either key can press a modifier, so use whichever held key was pressed first
+            InputKind::Modifier(modifier) => modifier
+                .key_codes()
+                .into_iter()
+                .filter(
+                    |&keycode| matches!(self.keycodes, Some(keycodes) if keycodes.pressed(keycode)),
+                )
+                .filter_map(|keycode| press_times.keycodes.get(&keycode).copied())
+                .min(),
+            InputKind::Mouse(mouse_button) => press_times.mouse_buttons.get(&mouse_button).copied(),
+            InputKind::GamepadButton(button_type) => {
+                let gamepad = self.guess_gamepad()?;
+                press_times
+                    .gamepad_buttons
+                    .get(&GamepadButton {
+                        gamepad,
+                        button_type,
+                    })
+                    .copied()
+            }
+            _ => None,
+        }
+    }
+
     /// Get the "value" of the input.
     ///
     /// For binary inputs such as buttons, this will always be either `0.0` or `1.0`. For analog
@@ -303,7 +396,10 @@ impl<'a> InputStreams<'a> {
                                 MouseMotionAxisType::Y => mouse_wheel_event.delta.y,
                             }
                         }
-                        value_in_axis_range(single_axis, total_mouse_motion_movement)
+                        let normalized_mouse_motion = self
+                            .mouse_motion_normalization
+                            .normalize(total_mouse_motion_movement, self.frame_time);
+                        value_in_axis_range(single_axis, normalized_mouse_motion)
                     }
                 }
             }
@@ -348,6 +444,25 @@ impl<'a> InputStreams<'a> {
     pub fn input_axis_pair(&self, input: &UserInput) -> Option<DualAxisData> {
         match input {
             UserInput::Single(InputKind::DualAxis(dual_axis)) => {
+                // Stick-like mouse motion is clamped by the length of the pair rather than per axis, like a gamepad stick
+                if let (
+                    MouseMotionNormalization::Stick { scale },
+                    AxisType::MouseMotion(_),
+                    AxisType::MouseMotion(_),
+                ) = (
+                    self.mouse_motion_normalization,
+                    dual_axis.x.axis_type,
+                    dual_axis.y.axis_type,
+                ) {
+                    let rate_streams = InputStreams {
+                        mouse_motion_normalization: MouseMotionNormalization::Rate { scale },
+                        ..self.clone()
+                    };
+                    let mut axis_pair = rate_streams.input_axis_pair(input)?;
+                    axis_pair.clamp_length(1.0);
+                    return Some(axis_pair);
+                }
+
                 let x = self.input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.x)));
                 let y = self.input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.y)));
 
@@ -378,6 +493,111 @@ impl<'a> InputStreams<'a> {
     }
 }
 
+/// Records when each key and button was last pressed, and when each [`InputDevice`] was last used
+///
+/// This is used to check the [`ChordTiming`] constraints of chords,
+/// as [`Input`] only stores whether each button is pressed,
+/// and to find the most recently used device for [`AggregationPolicy::MostRecentDevice`](crate::user_input::AggregationPolicy::MostRecentDevice).
+/// It is added and kept up to date by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin),
+/// using [`record_button_press_times`](crate::systems::record_button_press_times).
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ButtonPressTimes {
+    keycodes: HashMap<KeyCode, Instant>,
+    mouse_buttons: HashMap<MouseButton, Instant>,
+    gamepad_buttons: HashMap<GamepadButton, Instant>,
+    /// When the keyboard and mouse were last used
+    devices: HashMap<InputDevice, Instant>,
+    /// When each gamepad was last used
+    gamepads: HashMap<Gamepad, Instant>,
+    /// The gamepad axes that were outside of their deadzone when motion was last recorded
+    active_gamepad_axes: HashSet<GamepadAxis>,
+}
+
+impl ButtonPressTimes {
+    /// The gamepad axes whose motion counts as using a gamepad
+    const GAMEPAD_AXES: [GamepadAxisType; 6] = [
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        GamepadAxisType::LeftZ,
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+        GamepadAxisType::RightZ,
+    ];
+
+    /// Records the `instant` at which every key and button that was just pressed was pressed
+    pub fn record(
+        &mut self,
+        instant: Instant,
+        keycodes: Option<&Input<KeyCode>>,
+        mouse_buttons: Option<&Input<MouseButton>>,
+        gamepad_buttons: &Input<GamepadButton>,
+    ) {
+        if let Some(keycodes) = keycodes {
+            for &keycode in keycodes.get_just_pressed() {
+                self.keycodes.insert(keycode, instant);
+                self.devices.insert(InputDevice::Keyboard, instant);
+            }
+        }
+
+        if let Some(mouse_buttons) = mouse_buttons {
+            for &mouse_button in mouse_buttons.get_just_pressed() {
+                self.mouse_buttons.insert(mouse_button, instant);
+                self.devices.insert(InputDevice::Mouse, instant);
+            }
+        }
+
+        for &gamepad_button in gamepad_buttons.get_just_pressed() {
+            self.gamepad_buttons.insert(gamepad_button, instant);
+            self.gamepads.insert(gamepad_button.gamepad, instant);
+        }
+    }
+
+    /// Records the `instant` at which the mouse or the axes of each gamepad were used
+    ///
+    /// Gamepads are used when one of their axes leaves its deadzone of [`DualAxis::DEFAULT_DEADZONE`],
+    /// and the mouse is used whenever `mouse_moved` is `true`.
+    pub fn record_motion(
+        &mut self,
+        instant: Instant,
+        gamepad_axes: &Axis<GamepadAxis>,
+        gamepads: &Gamepads,
+        mouse_moved: bool,
+    ) {
+        if mouse_moved {
+            self.devices.insert(InputDevice::Mouse, instant);
+        }
+
+        for gamepad in gamepads.iter() {
+            for axis_type in Self::GAMEPAD_AXES {
+                let axis = GamepadAxis { gamepad, axis_type };
+                let active = gamepad_axes
+                    .get(axis)
+                    .is_some_and(|value| value.abs() > DualAxis::DEFAULT_DEADZONE);
+
+                // Holding a stick still does not count as using it again
+                if !active {
+                    self.active_gamepad_axes.remove(&axis);
+                } else if self.active_gamepad_axes.insert(axis) {
+                    self.gamepads.insert(gamepad, instant);
+                }
+            }
+        }
+    }
+
+    /// When was the `device` last used?
+    ///
+    /// If `device` is [`InputDevice::Gamepad`], only the provided `gamepad` is considered,
+    /// or every gamepad if it is [`None`].
+    #[must_use]
+    pub fn last_used(&self, device: InputDevice, gamepad: Option<Gamepad>) -> Option<Instant> {
+        match (device, gamepad) {
+            (InputDevice::Gamepad, Some(gamepad)) => self.gamepads.get(&gamepad).copied(),
+            (InputDevice::Gamepad, None) => self.gamepads.values().max().copied(),
+            (device, _) => self.devices.get(&device).copied(),
+        }
+    }
+}
+
 /// A mutable collection of [`Input`] structs, which can be used for mocking user inputs.
 ///
 /// These are typically collected via a system from the [`World`](bevy::prelude::World) as resources.
@@ -484,6 +704,9 @@ impl<'a> From<MutableInputStreams<'a>> for InputStreams<'a> {
             mouse_wheel: Some(mutable_streams.mouse_wheel),
             mouse_motion: mutable_streams.mouse_motion,
             associated_gamepad: mutable_streams.associated_gamepad,
+            press_times: None,
+            mouse_motion_normalization: MouseMotionNormalization::Raw,
+            frame_time: Duration::ZERO,
         }
     }
 }
@@ -500,6 +723,9 @@ impl<'a> From<&'a MutableInputStreams<'a>> for InputStreams<'a> {
             mouse_wheel: Some(mutable_streams.mouse_wheel),
             mouse_motion: mutable_streams.mouse_motion,
             associated_gamepad: mutable_streams.associated_gamepad,
+            press_times: None,
+            mouse_motion_normalization: MouseMotionNormalization::Raw,
+            frame_time: Duration::ZERO,
         }
     }
 }
@@ -5,26 +5,43 @@
 
 use crate::action_state::ActionState;
 use crate::input_map::InputMap;
+use crate::user_input::{InputKind, UserInput};
 use bevy::ecs::prelude::*;
 use std::marker::PhantomData;
 
+pub mod action_handlers;
 pub mod action_state;
+#[cfg(feature = "asset")]
+pub mod asset;
+pub mod axis_filters;
 pub mod axislike;
 pub mod buttonlike;
 pub mod clashing_inputs;
+pub mod cooldown;
 mod display_impl;
+pub mod dynamic_action;
 pub mod errors;
 pub mod input_map;
 pub mod input_mocking;
+pub mod input_set;
 pub mod input_streams;
 pub mod orientation;
 pub mod plugin;
 pub mod systems;
 pub mod user_input;
+pub mod versioning;
 
 // Importing the derive macro
 pub use leafwing_input_manager_macros::Actionlike;
 
+// Re-exported so that the code generated by the derive macro does not depend on how `bevy` is imported
+#[doc(hidden)]
+pub mod __macro_exports {
+    pub use bevy::input::gamepad::GamepadButtonType;
+    pub use bevy::input::keyboard::KeyCode;
+    pub use bevy::input::mouse::MouseButton;
+}
+
 /// Everything you need to get started
 pub mod prelude {
     pub use crate::action_state::{ActionState, ActionStateDriver};
@@ -37,7 +54,7 @@ pub mod prelude {
 
     pub use crate::plugin::InputManagerPlugin;
     pub use crate::plugin::ToggleActions;
-    pub use crate::{Actionlike, InputManagerBundle};
+    pub use crate::{ActionKind, ActionMetadata, Actionlike, InputManagerBundle};
 }
 
 /// Allows a type to be used as a gameplay action in an input-agnostic fashion
@@ -71,6 +88,175 @@ pub mod prelude {
 ///    Ultimate,
 /// }
 /// ```
+///
+/// # Default bindings
+///
+/// When deriving this trait, the default inputs for each action can be declared using `#[actionlike(...)]` attributes.
+/// Use `key` for [`KeyCode`](bevy::input::keyboard::KeyCode)s, `gamepad` for [`GamepadButtonType`](bevy::input::gamepad::GamepadButtonType)s
+/// and `mouse` for [`MouseButton`](bevy::input::mouse::MouseButton)s, naming the variant as a string.
+/// Each setting can be repeated to bind several inputs, and misspelled variants are reported as compile errors.
+///
+/// ```rust
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash)]
+/// enum PlayerAction {
+///     #[actionlike(key = "Space", gamepad = "South")]
+///     Jump,
+///     #[actionlike(key = "LShift", key = "RShift", mouse = "Right")]
+///     Run,
+///     // Not bound by default
+///     Emote,
+/// }
+///
+/// let input_map = InputMap::<PlayerAction>::default_bindings();
+/// assert_eq!(input_map.get(PlayerAction::Run).len(), 3);
+/// assert!(input_map.get(PlayerAction::Emote).is_empty());
+/// ```
+///
+/// ```compile_fail
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash)]
+/// enum PlayerAction {
+///     // There is no `KeyCode::Spacebar`
+///     #[actionlike(key = "Spacebar")]
+///     Jump,
+/// }
+/// ```
+///
+/// # Metadata
+///
+/// Binding menus and other UI can read human-readable information about each action from [`Actionlike::metadata`].
+/// When deriving this trait, this can be set with the `name`, `category`, `description` and `rebindable` settings.
+///
+/// ```rust
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash)]
+/// enum PlayerAction {
+///     #[actionlike(category = "Movement", description = "Leap into the air")]
+///     Jump,
+///     #[actionlike(name = "Open menu", category = "Interface", rebindable = false)]
+///     OpenMenu,
+/// }
+///
+/// assert_eq!(PlayerAction::Jump.metadata().name, "Jump");
+/// assert_eq!(PlayerAction::OpenMenu.metadata().name, "Open menu");
+/// assert!(!PlayerAction::OpenMenu.metadata().rebindable);
+/// ```
+///
+/// # Action kinds
+///
+/// By default, any input can be bound to any action, and actions store both a value and an axis pair.
+/// To catch mistakes like binding a joystick to a jump, declare the [`ActionKind`] of each action with the `kind` setting.
+///
+/// ```rust
+/// use bevy::input::gamepad::GamepadAxisType;
+/// use leafwing_input_manager::axislike::DualAxisData;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash)]
+/// enum PlayerAction {
+///     #[actionlike(kind = "Button", key = "Space")]
+///     Jump,
+///     #[actionlike(kind = "Axis")]
+///     Throttle,
+///     #[actionlike(kind = "DualAxis")]
+///     Move,
+/// }
+///
+/// let mut input_map = InputMap::<PlayerAction>::default_bindings();
+/// input_map.insert(DualAxis::left_stick(), PlayerAction::Move);
+/// input_map.insert(SingleAxis::symmetric(GamepadAxisType::RightZ, 0.1), PlayerAction::Throttle);
+///
+/// // A dual axis cannot be bound to a button
+/// assert!(input_map.try_insert(DualAxis::right_stick(), PlayerAction::Jump).is_err());
+///
+/// let action_state = ActionState::<PlayerAction>::default();
+/// assert_eq!(action_state.axis_value(PlayerAction::Throttle), 0.0);
+/// assert_eq!(action_state.dual_axis_pair(PlayerAction::Move), DualAxisData::default());
+/// ```
+///
+/// # Data-carrying variants
+///
+/// Variants with fields are normally treated as a single action, with their fields set to [`Default::default`].
+/// Instead, a variant with a single unnamed integer field can declare a `range` of values,
+/// each of which is a distinct action with its own index and bindings.
+/// Ranges may be exclusive (`0..10`) or inclusive (`1..=9`).
+///
+/// ```rust
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+/// enum PlayerAction {
+///     Jump,
+///     #[actionlike(range = 0..10)]
+///     Hotbar(u8),
+/// }
+///
+/// assert_eq!(PlayerAction::N_VARIANTS, 11);
+/// assert_eq!(PlayerAction::Hotbar(3).index(), 4);
+/// assert_eq!(PlayerAction::get_at(4), Some(PlayerAction::Hotbar(3)));
+/// ```
+///
+/// Calling [`Actionlike::index`] on a value outside of the declared range will panic.
+/// Default bindings cannot be declared on these variants: bind each value using an [`InputMap`] instead.
+///
+/// The range must fit in the type of the field, which is checked at compile time:
+///
+/// ```rust,compile_fail
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+/// enum PlayerAction {
+///     // `u8` cannot store values above 255
+///     #[actionlike(range = 0..300)]
+///     Hotbar(u8),
+/// }
+/// ```
+///
+/// # Nested actions
+///
+/// Groups of actions defined in separate enums can be composed into a single action type
+/// by marking variants that hold another [`Actionlike`] type as `nested`.
+/// Each nested action is a distinct action of the outer type,
+/// so a single [`InputMap`] and [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) can drive every group,
+/// and clashes between the inputs of different groups are resolved.
+///
+/// The default bindings and kinds of nested actions are those of the inner type.
+/// Their [`ActionMetadata::category`] defaults to the `category` setting of the variant, or its name.
+///
+/// ```rust
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+/// enum MovementAction {
+///     #[actionlike(key = "Space")]
+///     Jump,
+///     Crouch,
+/// }
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+/// enum MenuAction {
+///     Open,
+/// }
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
+/// enum PlayerAction {
+///     #[actionlike(nested)]
+///     Movement(MovementAction),
+///     #[actionlike(nested, category = "Interface")]
+///     Menu(MenuAction),
+/// }
+///
+/// assert_eq!(PlayerAction::N_VARIANTS, 3);
+/// assert_eq!(PlayerAction::Menu(MenuAction::Open).index(), 2);
+/// assert_eq!(PlayerAction::Movement(MovementAction::Jump).metadata().category, Some("Movement"));
+///
+/// let input_map = InputMap::<PlayerAction>::default_bindings();
+/// assert_eq!(input_map.get(PlayerAction::Movement(MovementAction::Jump)).len(), 1);
+/// ```
 pub trait Actionlike: Send + Sync + Clone + 'static {
     /// The number of variants of this action type
     const N_VARIANTS: usize;
@@ -86,7 +272,110 @@ pub trait Actionlike: Send + Sync + Clone + 'static {
     fn get_at(index: usize) -> Option<Self>;
 
     /// Returns the position in the defining enum of the given action
+    ///
+    /// # Panics
+    ///
+    /// When derived, panics if the value stored in a variant with a declared `range` is outside of that range.
     fn index(&self) -> usize;
+
+    /// The inputs bound to each action by default
+    ///
+    /// When deriving this trait, these are declared using `#[actionlike(...)]` attributes on each variant.
+    /// Use [`InputMap::default_bindings`] to construct an [`InputMap`] from them.
+    ///
+    /// By default, no inputs are bound.
+    fn default_bindings() -> Vec<(UserInput, Self)> {
+        Vec::new()
+    }
+
+    /// Human-readable information about this action, for use in binding menus and other UI
+    ///
+    /// When deriving this trait, this is declared using `#[actionlike(...)]` attributes on each variant,
+    /// and the name defaults to the name of the variant.
+    ///
+    /// By default, the returned [`ActionMetadata`] has an empty name and no category or description,
+    /// and the action is rebindable.
+    fn metadata(&self) -> ActionMetadata {
+        ActionMetadata::default()
+    }
+
+    /// The kind of data that this action represents, if declared
+    ///
+    /// When deriving this trait, this is declared using `#[actionlike(kind = "...")]` attributes on each variant.
+    /// Bindings that are incompatible with the declared kind are rejected by [`InputMap`],
+    /// and the typed accessors of [`ActionState`] check that they are used with the right kind of action.
+    ///
+    /// By default, the kind is not declared, and any input can be bound to the action.
+    fn kind(&self) -> Option<ActionKind> {
+        None
+    }
+}
+
+/// The kind of data that an [`Actionlike`] action represents
+///
+/// Declared using [`Actionlike::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionKind {
+    /// A button, which is either pressed or released
+    ///
+    /// Single axes can be bound to buttons, and are pressed when they are outside of their deadzone.
+    Button,
+    /// A single axis, read using [`ActionState::axis_value`]
+    ///
+    /// Buttons can be bound to axes, and have a value of 1.0 when pressed.
+    Axis,
+    /// A pair of axes, read using [`ActionState::dual_axis_pair`]
+    ///
+    /// Only inputs that provide a [`DualAxisData`](crate::axislike::DualAxisData) can be bound to dual axes:
+    /// [`DualAxis`](crate::axislike::DualAxis), [`VirtualDPad`](crate::axislike::VirtualDPad) and chords containing a [`DualAxis`](crate::axislike::DualAxis).
+    DualAxis,
+}
+
+impl ActionKind {
+    /// Can the `input` be bound to an action of this kind?
+    #[must_use]
+    pub fn accepts(&self, input: &UserInput) -> bool {
+        let provides_axis_pair = match input {
+            UserInput::Single(InputKind::DualAxis(_)) | UserInput::VirtualDPad(_) => true,
+            UserInput::Chord(chord) => chord
+                .iter()
+                .any(|input_kind| matches!(input_kind, InputKind::DualAxis(_))),
+            UserInput::Single(_) | UserInput::VirtualAxis(_) => false,
+        };
+
+        match self {
+            ActionKind::Button | ActionKind::Axis => !provides_axis_pair,
+            ActionKind::DualAxis => provides_axis_pair,
+        }
+    }
+}
+
+/// Human-readable information about an [`Actionlike`] action
+///
+/// Returned by [`Actionlike::metadata`], allowing binding menus to be driven entirely by your action type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActionMetadata {
+    /// The name of the action, as displayed to players
+    pub name: &'static str,
+    /// The group of related actions that this action belongs to, such as "Movement" or "Abilities"
+    pub category: Option<&'static str>,
+    /// A longer explanation of what this action does, suitable for a tooltip
+    pub description: Option<&'static str>,
+    /// Can players change the bindings of this action?
+    ///
+    /// This is not enforced by [`InputMap`]; it is up to your binding menu to respect it.
+    pub rebindable: bool,
+}
+
+impl Default for ActionMetadata {
+    fn default() -> Self {
+        ActionMetadata {
+            name: "",
+            category: None,
+            description: None,
+            rebindable: true,
+        }
+    }
 }
 
 /// An iterator of [`Actionlike`] actions
@@ -95,6 +384,11 @@ pub trait Actionlike: Send + Sync + Clone + 'static {
 #[derive(Debug, Clone)]
 pub struct ActionIter<A: Actionlike> {
     index: usize,
+    /// The number of actions when the iterator was created
+    ///
+    /// Not every index below `N_VARIANTS` has to be an action, as with `DynamicAction`,
+    /// so this is counted once, and actions added afterwards are not returned.
+    end: usize,
     _phantom: PhantomData<A>,
 }
 
@@ -102,6 +396,10 @@ impl<A: Actionlike> Iterator for ActionIter<A> {
     type Item = A;
 
     fn next(&mut self) -> Option<A> {
+        if self.index >= self.end {
+            return None;
+        }
+
         let item = A::get_at(self.index);
         if item.is_some() {
             self.index += 1;
@@ -109,19 +407,23 @@ impl<A: Actionlike> Iterator for ActionIter<A> {
 
         item
     }
-}
 
-impl<A: Actionlike> ExactSizeIterator for ActionIter<A> {
-    fn len(&self) -> usize {
-        A::N_VARIANTS
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
     }
 }
 
+impl<A: Actionlike> ExactSizeIterator for ActionIter<A> {}
+
 // We can't derive this, because otherwise it won't work when A is not default
 impl<A: Actionlike> Default for ActionIter<A> {
     fn default() -> Self {
         ActionIter {
             index: 0,
+            end: (0..A::N_VARIANTS)
+                .take_while(|&index| A::get_at(index).is_some())
+                .count(),
             _phantom: PhantomData::default(),
         }
     }
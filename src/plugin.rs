@@ -1,7 +1,11 @@
 //! Contains main plugin exported by this crate.
 
+use crate::action_state::ActionEvent;
+use crate::axis_filters::AxisFilterState;
 use crate::clashing_inputs::ClashStrategy;
+use crate::input_streams::ButtonPressTimes;
 use crate::prelude::ActionState;
+use crate::systems::*;
 use crate::Actionlike;
 use core::hash::Hash;
 use core::marker::PhantomData;
@@ -9,9 +13,12 @@ use std::fmt::Debug;
 
 use bevy::app::{App, CoreStage, Plugin};
 use bevy::ecs::prelude::*;
+use bevy::ecs::schedule::{ShouldRun, StageLabelId, StateData};
+use bevy::ecs::system::BoxedSystem;
 use bevy::input::InputSystem;
 #[cfg(feature = "ui")]
 use bevy::ui::UiSystem;
+use bevy::utils::Duration;
 
 /// A [`Plugin`] that collects [`Input`](bevy::input::Input) from disparate sources, producing an [`ActionState`](crate::action_state::ActionState) that can be conveniently checked
 ///
@@ -30,7 +37,7 @@ use bevy::ui::UiSystem;
 /// All systems added by this plugin can be dynamically enabled and disabled by setting the value of the [`ToggleActions<A>`] resource is set.
 /// This can be useful when working with states to pause the game, navigate menus or so on.
 ///
-/// **WARNING:** Theses systems run during [`CoreStage::PreUpdate`].
+/// **WARNING:** Theses systems run during [`CoreStage::PreUpdate`], unless configured otherwise.
 /// If you have systems that care about inputs and actions that also run during this stage,
 /// you must define an ordering between your systems or behavior will be very erratic.
 /// The stable labels for these systems are available under [`InputManagerSystem`] enum.
@@ -39,28 +46,111 @@ use bevy::ui::UiSystem;
 ///
 /// - [`tick_action_state`](crate::systems::tick_action_state), which resets the `pressed` and `just_pressed` fields of the [`ActionState`](crate::action_state::ActionState) each frame
 ///     - labeled [`InputManagerSystem::Reset`]
+///     - replaced by [`tick_action_state_fixed`](crate::systems::tick_action_state_fixed) at the end of each fixed step when using [`InputManagerPlugin::fixed_timestep`]
 /// - [`update_action_state`](crate::systems::update_action_state), which collects [`Input`](bevy::input::Input) resources to update the [`ActionState`](crate::action_state::ActionState)
 ///     - labeled [`InputManagerSystem::Update`]
+///     - also sends an [`ActionEvent`] for each change when using [`InputManagerPlugin::with_action_events`]
+/// - [`run_action_handlers`](crate::systems::run_action_handlers), which runs the [`ActionHandlers`](crate::action_handlers::ActionHandlers) of each entity
+///     - only added when using [`InputManagerPlugin::with_action_handlers`]
+///     - labeled [`InputManagerSystem::RunHandlers`]
+///     - runs at the end of the stage, after all other systems
+///     - runs at the end of each fixed step, just before it is ticked, when using [`InputManagerPlugin::fixed_timestep`]
+/// - [`record_button_press_times`](crate::systems::record_button_press_times), which records when each button was pressed in [`ButtonPressTimes`]
+///     - used to check the [`ChordTiming`](crate::user_input::ChordTiming) constraints of chords
+///     - only added once, no matter how many copies of this plugin are added
 /// - [`update_action_state_from_interaction`](crate::systems::update_action_state_from_interaction), for triggering actions from buttons
 ///    - powers the [`ActionStateDriver`](crate::action_state::ActionStateDriver) component baseod on an [`Interaction`](bevy::ui::Interaction) component
 ///    - labeled [`InputManagerSystem::Update`]
 /// - [`release_on_disable`](crate::systems::release_on_disable), which resets action states when [`ToggleActions`] is flipped, to avoid persistent presses.
+/// - [`release_on_input_map_removed`](crate::systems::release_on_input_map_removed), which releases actions when their [`InputMap`](crate::input_map::InputMap) is removed
+///     - runs during [`CoreStage::PostUpdate`]
+/// - [`toggle_actions_in_state`](crate::systems::toggle_actions_in_state), which enables and disables actions as the [`State`] changes
+///     - only added when using [`InputManagerPlugin::active_in_state`]
+///     - runs during [`CoreStage::PostUpdate`]
+///
+/// ## Configuration
+///
+/// The stages these systems run in, the conditions under which they run and which optional systems are included
+/// can be changed using the builder methods of this plugin, so that it fits into custom schedules:
+///
+/// ```rust
+/// use bevy::ecs::schedule::ShouldRun;
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy)]
+/// enum Action {
+///     Jump,
+/// }
+///
+/// #[derive(Resource)]
+/// struct Paused(bool);
+///
+/// fn unless_paused(paused: Res<Paused>) -> ShouldRun {
+///     if paused.0 {
+///         ShouldRun::No
+///     } else {
+///         ShouldRun::Yes
+///     }
+/// }
+///
+/// App::new()
+///     .insert_resource(Paused(false))
+///     .add_plugin(
+///         InputManagerPlugin::<Action>::default()
+///             .in_stage(CoreStage::First)
+///             .run_if(unless_paused)
+///             .without_release_on_input_map_removed(),
+///     );
+/// ```
 pub struct InputManagerPlugin<A: Actionlike> {
     _phantom: PhantomData<A>,
     machine: Machine,
+    ticking: Ticking,
+    input_stage: StageLabelId,
+    cleanup_stage: StageLabelId,
+    run_criteria: Option<RunCriteriaFactory>,
+    #[cfg(feature = "ui")]
+    ui_interaction: bool,
+    release_on_input_map_removed: bool,
+    action_events: bool,
+    action_handlers: bool,
+    state_scope: Option<StateScope>,
 }
 
+/// Adds the system that ties [`ToggleActions`] to a [`State`] to the provided stage
+///
+/// This is boxed so that the plugin does not need to be generic over the state type.
+type StateScope = Box<dyn Fn(&mut App, StageLabelId) + Send + Sync>;
+
+/// Creates a new copy of the run criteria for each system, as systems cannot be shared
+type RunCriteriaFactory = Box<dyn Fn() -> BoxedSystem<(), ShouldRun> + Send + Sync>;
+
 // Deriving default induces an undesired bound on the generic
 impl<A: Actionlike> Default for InputManagerPlugin<A> {
     fn default() -> Self {
-        Self {
-            _phantom: PhantomData::default(),
-            machine: Machine::Client,
-        }
+        Self::new(Machine::Client, Ticking::PerFrame)
     }
 }
 
 impl<A: Actionlike> InputManagerPlugin<A> {
+    fn new(machine: Machine, ticking: Ticking) -> Self {
+        Self {
+            _phantom: PhantomData,
+            machine,
+            ticking,
+            input_stage: CoreStage::PreUpdate.as_label(),
+            cleanup_stage: CoreStage::PostUpdate.as_label(),
+            run_criteria: None,
+            #[cfg(feature = "ui")]
+            ui_interaction: true,
+            release_on_input_map_removed: true,
+            action_events: false,
+            action_handlers: false,
+            state_scope: None,
+        }
+    }
+
     /// Creates a version of the plugin intended to run on the server
     ///
     /// Inputs will not be processed; instead, [`ActionState`](crate::action_state::ActionState)
@@ -68,9 +158,149 @@ impl<A: Actionlike> InputManagerPlugin<A> {
     /// or constructed from [`ActionDiff`](crate::action_state::ActionDiff) event streams.
     #[must_use]
     pub fn server() -> Self {
-        Self {
-            _phantom: PhantomData::default(),
-            machine: Machine::Server,
+        Self::new(Machine::Server, Ticking::PerFrame)
+    }
+
+    /// Creates a version of the plugin whose [`ActionState`](crate::action_state::ActionState)s advance once per fixed timestep, rather than once per frame
+    ///
+    /// Use this when your gameplay systems run in a `stage` with a fixed timestep run criteria,
+    /// such as [`FixedTimestep`](bevy::time::FixedTimestep), whose length is `step`.
+    /// Inputs are still read every frame, but actions are only ticked at the end of each run of the fixed `stage`,
+    /// so each press is seen as [`just_pressed`](crate::action_state::ActionState::just_pressed) by exactly one fixed step,
+    /// even if no fixed steps or several fixed steps run in the frame it occurs.
+    /// Durations are measured in fixed-step time.
+    ///
+    /// Systems outside of the fixed `stage` will see actions as just pressed for every frame until the next fixed step.
+    ///
+    /// The `stage` must be added to your app before this plugin.
+    #[must_use]
+    pub fn fixed_timestep(stage: impl StageLabel, step: Duration) -> Self {
+        Self::new(
+            Machine::Client,
+            Ticking::FixedTimestep {
+                stage: stage.as_label(),
+                step,
+            },
+        )
+    }
+
+    /// Runs the systems that tick and update actions in the provided `stage`, rather than [`CoreStage::PreUpdate`]
+    ///
+    /// The `stage` must be added to your app before this plugin.
+    #[must_use]
+    pub fn in_stage(mut self, stage: impl StageLabel) -> Self {
+        self.input_stage = stage.as_label();
+        self
+    }
+
+    /// Runs [`release_on_input_map_removed`](crate::systems::release_on_input_map_removed) and [`toggle_actions_in_state`](crate::systems::toggle_actions_in_state)
+    /// in the provided `stage`, rather than [`CoreStage::PostUpdate`]
+    ///
+    /// The `stage` must be added to your app before this plugin.
+    #[must_use]
+    pub fn cleanup_in_stage(mut self, stage: impl StageLabel) -> Self {
+        self.cleanup_stage = stage.as_label();
+        self
+    }
+
+    /// Only ticks and updates actions when the provided `run_criteria` returns [`ShouldRun::Yes`]
+    ///
+    /// This is checked in addition to [`ToggleActions<A>`]: actions are only processed if both allow it.
+    /// Unlike [`ToggleActions<A>`], actions are not released when the `run_criteria` stops them from being processed.
+    ///
+    /// As each system needs its own copy, the `run_criteria` must be [`Clone`], as function systems are.
+    #[must_use]
+    pub fn run_if<Marker>(
+        mut self,
+        run_criteria: impl IntoSystem<(), ShouldRun, Marker> + Clone + Send + Sync + 'static,
+    ) -> Self {
+        self.run_criteria = Some(Box::new(move || {
+            Box::new(run_criteria.clone().pipe(and_if_enabled::<A>))
+        }));
+        self
+    }
+
+    /// Only enables actions while the [`State<S>`] is `active_state`
+    ///
+    /// [`ToggleActions<A>`] is enabled when entering the `active_state` and disabled when leaving it,
+    /// releasing all actions, so you do not need to write systems that do so on state transitions.
+    /// [`ToggleActions<A>`] is updated in the same stage as [`release_on_input_map_removed`](crate::systems::release_on_input_map_removed),
+    /// after [`State`] transitions have been applied, so the change takes effect at the start of the following frame.
+    ///
+    /// Setting [`ToggleActions<A>`] manually will be overridden while the `State<S>` resource exists.
+    /// Calling this again replaces the previously configured state.
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    /// enum AppState {
+    ///     Menu,
+    ///     Playing,
+    /// }
+    ///
+    /// #[derive(Actionlike, Clone, Copy)]
+    /// enum MenuAction {
+    ///     Select,
+    ///     Back,
+    /// }
+    ///
+    /// App::new()
+    ///     .add_state(AppState::Menu)
+    ///     .add_plugin(InputManagerPlugin::<MenuAction>::default().active_in_state(AppState::Menu));
+    /// ```
+    #[must_use]
+    pub fn active_in_state<S: StateData>(mut self, active_state: S) -> Self {
+        self.state_scope = Some(Box::new(move |app, stage| {
+            app.add_system_to_stage(stage, toggle_actions_in_state::<A, S>(active_state.clone()));
+        }));
+        self
+    }
+
+    /// Does not add [`update_action_state_from_interaction`](crate::systems::update_action_state_from_interaction),
+    /// for apps that do not use [`ActionStateDriver`](crate::action_state::ActionStateDriver)s
+    #[cfg(feature = "ui")]
+    #[must_use]
+    pub fn without_ui_interaction(mut self) -> Self {
+        self.ui_interaction = false;
+        self
+    }
+
+    /// Does not add [`release_on_input_map_removed`](crate::systems::release_on_input_map_removed),
+    /// so removing an [`InputMap`](crate::input_map::InputMap) leaves its actions in their current state
+    #[must_use]
+    pub fn without_release_on_input_map_removed(mut self) -> Self {
+        self.release_on_input_map_removed = false;
+        self
+    }
+
+    /// Adds an [`Events<ActionEvent<A>>`](ActionEvent) resource,
+    /// which [`update_action_state`](crate::systems::update_action_state) fills with an [`ActionEvent`] for each change to an [`ActionState`]
+    ///
+    /// Read these events with an [`EventReader<ActionEvent<A>>`](EventReader) to respond to actions
+    /// without checking the [`ActionState`] of every entity.
+    #[must_use]
+    pub fn with_action_events(mut self) -> Self {
+        self.action_events = true;
+        self
+    }
+
+    /// Adds [`run_action_handlers`](crate::systems::run_action_handlers),
+    /// which runs the [`ActionHandlers`](crate::action_handlers::ActionHandlers) component of each entity after its [`ActionState`] is updated
+    ///
+    /// This is an exclusive system, so it is only added when requested.
+    #[must_use]
+    pub fn with_action_handlers(mut self) -> Self {
+        self.action_handlers = true;
+        self
+    }
+
+    /// Creates the run criteria for a system that processes inputs
+    fn run_criteria(&self) -> BoxedSystem<(), ShouldRun> {
+        match &self.run_criteria {
+            Some(run_criteria) => run_criteria(),
+            None => Box::new(IntoSystem::into_system(run_if_enabled::<A>)),
         }
     }
 }
@@ -81,70 +311,143 @@ enum Machine {
     Client,
 }
 
+/// How often are actions ticked?
+enum Ticking {
+    PerFrame,
+    FixedTimestep { stage: StageLabelId, step: Duration },
+}
+
 impl<A: Actionlike> Plugin for InputManagerPlugin<A> {
     fn build(&self, app: &mut App) {
-        use crate::systems::*;
+        let input_stage = self.input_stage;
 
         match self.machine {
             Machine::Client => {
+                match self.ticking {
+                    Ticking::PerFrame => {
+                        app.add_system_to_stage(
+                            input_stage,
+                            tick_action_state::<A>
+                                .with_run_criteria(self.run_criteria())
+                                .label(InputManagerSystem::Tick)
+                                .before(InputManagerSystem::Update),
+                        );
+                    }
+                    Ticking::FixedTimestep { stage, step } => {
+                        app.init_resource::<FixedTimestepTicking<A>>()
+                            .add_system_to_stage(
+                                stage,
+                                tick_action_state_fixed::<A>(step)
+                                    .with_run_criteria(self.run_criteria())
+                                    .label(InputManagerSystem::Tick)
+                                    .at_end(),
+                            );
+                    }
+                }
+
                 app.add_system_to_stage(
-                    CoreStage::PreUpdate,
-                    tick_action_state::<A>
-                        .with_run_criteria(run_if_enabled::<A>)
-                        .label(InputManagerSystem::Tick)
-                        .before(InputManagerSystem::Update),
-                )
-                .add_system_to_stage(
-                    CoreStage::PreUpdate,
+                    input_stage,
                     release_on_disable::<A>
                         .label(InputManagerSystem::ReleaseOnDisable)
                         .after(InputManagerSystem::Update),
-                )
-                .add_system_to_stage(CoreStage::PostUpdate, release_on_input_map_removed::<A>);
+                );
+
+                if self.release_on_input_map_removed {
+                    app.add_system_to_stage(self.cleanup_stage, release_on_input_map_removed::<A>);
+                }
+
+                // Press times are shared by every action type
+                if !app.world.contains_resource::<ButtonPressTimes>() {
+                    app.init_resource::<ButtonPressTimes>().add_system_to_stage(
+                        input_stage,
+                        record_button_press_times
+                            .after(InputSystem)
+                            .before(InputManagerSystem::Update),
+                    );
+                }
+
+                if self.action_events {
+                    app.add_event::<ActionEvent<A>>();
+                }
+
+                // Used when the `InputMap` is a resource
+                app.init_resource::<AxisFilterState<A>>();
+
+                if self.action_handlers {
+                    match self.ticking {
+                        // Exclusive systems at the end of the stage run after all of the other systems
+                        Ticking::PerFrame => {
+                            app.add_system_to_stage(
+                                input_stage,
+                                run_action_handlers::<A>
+                                    .with_run_criteria(self.run_criteria())
+                                    .label(InputManagerSystem::RunHandlers)
+                                    .at_end(),
+                            );
+                        }
+                        // Actions stay just pressed until the end of the next fixed step
+                        Ticking::FixedTimestep { stage, .. } => {
+                            app.add_system_to_stage(
+                                stage,
+                                run_action_handlers::<A>
+                                    .with_run_criteria(self.run_criteria())
+                                    .label(InputManagerSystem::RunHandlers)
+                                    .before(InputManagerSystem::Tick)
+                                    .at_end(),
+                            );
+                        }
+                    }
+                }
 
                 #[cfg(feature = "egui")]
                 app.add_system_to_stage(
-                    CoreStage::PreUpdate,
+                    input_stage,
                     update_action_state::<A>
-                        .with_run_criteria(run_if_enabled::<A>)
+                        .with_run_criteria(self.run_criteria())
                         .label(InputManagerSystem::Update)
                         .after(InputSystem)
                         .after(bevy_egui::EguiSystem::ProcessInput),
                 );
                 #[cfg(not(feature = "egui"))]
                 app.add_system_to_stage(
-                    CoreStage::PreUpdate,
+                    input_stage,
                     update_action_state::<A>
-                        .with_run_criteria(run_if_enabled::<A>)
+                        .with_run_criteria(self.run_criteria())
                         .label(InputManagerSystem::Update)
                         .after(InputSystem),
                 );
 
                 #[cfg(feature = "ui")]
-                app.add_system_to_stage(
-                    CoreStage::PreUpdate,
-                    update_action_state_from_interaction::<A>
-                        .with_run_criteria(run_if_enabled::<A>)
-                        .label(InputManagerSystem::ManualControl)
-                        .before(InputManagerSystem::ReleaseOnDisable)
-                        .after(InputManagerSystem::Tick)
-                        // Must run after the system is updated from inputs, or it will be forcibly released due to the inputs
-                        // not being pressed
-                        .after(InputManagerSystem::Update)
-                        .after(UiSystem::Focus)
-                        .after(InputSystem),
-                );
+                if self.ui_interaction {
+                    app.add_system_to_stage(
+                        input_stage,
+                        update_action_state_from_interaction::<A>
+                            .with_run_criteria(self.run_criteria())
+                            .label(InputManagerSystem::ManualControl)
+                            .before(InputManagerSystem::ReleaseOnDisable)
+                            .after(InputManagerSystem::Tick)
+                            // Must run after the system is updated from inputs, or it will be forcibly released due to the inputs
+                            // not being pressed
+                            .after(InputManagerSystem::Update)
+                            .after(UiSystem::Focus)
+                            .after(InputSystem),
+                    );
+                }
             }
             Machine::Server => {
                 app.add_system_to_stage(
-                    CoreStage::PreUpdate,
+                    input_stage,
                     tick_action_state::<A>
-                        .with_run_criteria(run_if_enabled::<A>)
+                        .with_run_criteria(self.run_criteria())
                         .label(InputManagerSystem::Tick),
                 );
             }
         };
 
+        if let Some(state_scope) = &self.state_scope {
+            state_scope(app, self.cleanup_stage);
+        }
+
         app.register_type::<ActionState<A>>()
             // Resources
             .init_resource::<ToggleActions<A>>()
@@ -188,6 +491,25 @@ impl<A: Actionlike> Default for ToggleActions<A> {
     }
 }
 
+/// Marks that the [`ActionState`](crate::action_state::ActionState)s of type `A` are ticked once per fixed timestep
+///
+/// This resource is added by [`InputManagerPlugin::fixed_timestep`].
+/// While it exists, [`update_action_state`](crate::systems::update_action_state) uses [`ActionState::update_buffered`](crate::action_state::ActionState::update_buffered),
+/// so that actions are not released before the fixed step after they were pressed.
+#[derive(Resource)]
+pub struct FixedTimestepTicking<A: Actionlike> {
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for FixedTimestepTicking<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
 /// [`SystemLabel`]s for the [`crate::systems`] used by this crate
 ///
 /// `Reset` must occur before `Update`
@@ -205,4 +527,6 @@ pub enum InputManagerSystem {
     ///
     /// Must run after [`InputManagerSystem::Update`] or the action state will be overriden
     ManualControl,
+    /// Runs the [`ActionHandlers`](crate::action_handlers::ActionHandlers) of each entity
+    RunHandlers,
 }
@@ -3,15 +3,21 @@
 #[cfg(feature = "ui")]
 use crate::action_state::ActionStateDriver;
 use crate::{
-    action_state::{ActionDiff, ActionState},
+    action_handlers::ActionHandlers,
+    action_state::{ActionData, ActionDiff, ActionEvent, ActionState},
+    axis_filters::AxisFilterState,
     clashing_inputs::ClashStrategy,
+    cooldown::Cooldowns,
     input_map::InputMap,
-    input_streams::InputStreams,
-    plugin::ToggleActions,
+    input_streams::{ButtonPressTimes, InputStreams},
+    plugin::{FixedTimestepTicking, ToggleActions},
     Actionlike,
 };
 
-use bevy::ecs::{prelude::*, schedule::ShouldRun};
+use bevy::ecs::{
+    prelude::*,
+    schedule::{ShouldRun, StateData},
+};
 use bevy::input::{
     gamepad::{GamepadAxis, GamepadButton, Gamepads},
     keyboard::KeyCode,
@@ -19,7 +25,7 @@ use bevy::input::{
     Axis, Input,
 };
 use bevy::time::Time;
-use bevy::utils::Instant;
+use bevy::utils::{Duration, Instant};
 
 #[cfg(feature = "ui")]
 use bevy::ui::Interaction;
@@ -30,9 +36,13 @@ use bevy_egui::EguiContext;
 ///
 /// Clears the just-pressed and just-released values of all [`ActionState`]s.
 /// Also resets the internal `pressed_this_tick` field, used to track whether or not to release an action.
+///
+/// Any [`Cooldowns`] are advanced by the same amount of time.
 pub fn tick_action_state<A: Actionlike>(
     mut query: Query<&mut ActionState<A>>,
     action_state: Option<ResMut<ActionState<A>>>,
+    mut cooldowns_query: Query<&mut Cooldowns<A>>,
+    cooldowns: Option<ResMut<Cooldowns<A>>>,
     time: Res<Time>,
     mut stored_previous_instant: Local<Option<Instant>>,
 ) {
@@ -52,13 +62,92 @@ pub fn tick_action_state<A: Actionlike>(
         action_state.tick(current_instant, previous_instant);
     }
 
+    let delta = current_instant - previous_instant;
+    if let Some(mut cooldowns) = cooldowns {
+        cooldowns.tick(delta);
+    }
+
+    for mut cooldowns in cooldowns_query.iter_mut() {
+        cooldowns.tick(delta);
+    }
+
     // Store the previous time in the system
     *stored_previous_instant = time.last_update();
 }
 
+/// Creates an exclusive system that advances actions by a single fixed timestep of length `step`
+///
+/// This is used instead of [`tick_action_state`] by [`InputManagerPlugin::fixed_timestep`](crate::plugin::InputManagerPlugin::fixed_timestep),
+/// and runs at the end of the fixed timestep stage.
+/// As [`ActionState::update_buffered`] keeps actions that were just pressed until they are ticked,
+/// each press is seen as just pressed by exactly one fixed step, no matter how many frames pass between steps.
+///
+/// Durations are measured in fixed-step time: each run advances the clock by `step`, starting from [`Time::startup`].
+pub fn tick_action_state_fixed<A: Actionlike>(
+    step: Duration,
+) -> impl FnMut(&mut World) + Send + Sync + 'static {
+    let mut previous_instant: Option<Instant> = None;
+
+    move |world: &mut World| {
+        let previous = *previous_instant.get_or_insert_with(|| world.resource::<Time>().startup());
+        let current = previous + step;
+
+        if let Some(mut action_state) = world.get_resource_mut::<ActionState<A>>() {
+            action_state.tick(current, previous);
+        }
+
+        for mut action_state in world.query::<&mut ActionState<A>>().iter_mut(world) {
+            action_state.tick(current, previous);
+        }
+
+        if let Some(mut cooldowns) = world.get_resource_mut::<Cooldowns<A>>() {
+            cooldowns.tick(step);
+        }
+
+        for mut cooldowns in world.query::<&mut Cooldowns<A>>().iter_mut(world) {
+            cooldowns.tick(step);
+        }
+
+        previous_instant = Some(current);
+    }
+}
+
+/// Records when each key and button was pressed, and when each input device was used,
+/// in the [`ButtonPressTimes`] resource, using the [`Time`] of the current frame
+///
+/// Missing input resources will be ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn record_button_press_times(
+    time: Res<Time>,
+    mut press_times: ResMut<ButtonPressTimes>,
+    keycodes: Option<Res<Input<KeyCode>>>,
+    mouse_buttons: Option<Res<Input<MouseButton>>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepads: Res<Gamepads>,
+    mouse_motion: Res<Events<MouseMotion>>,
+    mouse_wheel: Option<Res<Events<MouseWheel>>>,
+) {
+    let instant = time.last_update().unwrap_or_else(|| time.startup());
+    press_times.record(
+        instant,
+        keycodes.as_deref(),
+        mouse_buttons.as_deref(),
+        &gamepad_buttons,
+    );
+
+    let mouse_moved = mouse_motion.iter_current_update_events().next().is_some()
+        || matches!(mouse_wheel, Some(mouse_wheel) if mouse_wheel.iter_current_update_events().next().is_some());
+    press_times.record_motion(instant, &gamepad_axes, &gamepads, mouse_moved);
+}
+
 /// Fetches all of the releveant [`Input`] resources to update [`ActionState`] according to the [`InputMap`]
 ///
-/// Missing resources will be ignored, and treated as if none of the corresponding inputs were pressed
+/// Missing resources will be ignored, and treated as if none of the corresponding inputs were pressed.
+/// If the [`Events<ActionEvent<A>>`](ActionEvent) resource exists, an [`ActionEvent`] is sent for each change to an [`ActionState`].
+/// The [`AxisFilter`](crate::axis_filters::AxisFilter)s of each [`InputMap`] are applied using the [`AxisFilterState`] stored alongside it,
+/// and advanced by the [`Time`] since the previous frame.
+/// Entities whose bindings are filtered are given an [`AxisFilterState`] component if they do not have one.
 #[allow(clippy::too_many_arguments)]
 pub fn update_action_state<A: Actionlike>(
     gamepad_buttons: Res<Input<GamepadButton>>,
@@ -73,7 +162,21 @@ pub fn update_action_state<A: Actionlike>(
     #[cfg(feature = "egui")] maybe_egui: Option<ResMut<EguiContext>>,
     mut action_state: Option<ResMut<ActionState<A>>>,
     mut input_map: Option<ResMut<InputMap<A>>>,
-    mut query: Query<(&mut ActionState<A>, &InputMap<A>)>,
+    mut query: Query<(
+        Entity,
+        &mut ActionState<A>,
+        &InputMap<A>,
+        Option<&mut AxisFilterState<A>>,
+    )>,
+    mut action_events: Option<ResMut<Events<ActionEvent<A>>>>,
+    press_times: Option<Res<ButtonPressTimes>>,
+    // Grouped to stay within the limit on the number of system parameters
+    (time, mut filter_state, fixed_timestep, mut commands): (
+        Res<Time>,
+        Option<ResMut<AxisFilterState<A>>>,
+        Option<Res<FixedTimestepTicking<A>>>,
+        Commands,
+    ),
 ) {
     let gamepad_buttons = gamepad_buttons.into_inner();
     let gamepad_button_axes = gamepad_button_axes.into_inner();
@@ -83,6 +186,9 @@ pub fn update_action_state<A: Actionlike>(
     let mouse_buttons = mouse_buttons.map(|mouse_buttons| mouse_buttons.into_inner());
     let mouse_wheel = mouse_wheel.map(|mouse_wheel| mouse_wheel.into_inner());
     let mouse_motion = mouse_motion.into_inner();
+    let press_times = press_times.map(|press_times| press_times.into_inner());
+    let delta = time.delta();
+    let buffered = fixed_timestep.is_some();
 
     #[cfg(feature = "egui")]
     let (keycodes, mouse_buttons, mouse_wheel) = if let Some(mut egui) = maybe_egui {
@@ -113,12 +219,31 @@ pub fn update_action_state<A: Actionlike>(
             mouse_wheel,
             mouse_motion,
             associated_gamepad: input_map.gamepad(),
+            press_times,
+            mouse_motion_normalization: input_map.mouse_motion_normalization(),
+            frame_time: delta,
         };
 
-        action_state.update(input_map.which_pressed(&input_streams, *clash_strategy));
+        let action_data = match &mut filter_state {
+            Some(filter_state) => input_map.which_pressed_filtered(
+                &input_streams,
+                *clash_strategy,
+                filter_state,
+                delta,
+            ),
+            None => input_map.which_pressed(&input_streams, *clash_strategy),
+        };
+        action_state.update_held_triggers(input_map.held_triggers(&input_streams, *clash_strategy));
+        apply_action_data(
+            action_state,
+            action_data,
+            None,
+            buffered,
+            &mut action_events,
+        );
     }
 
-    for (mut action_state, input_map) in query.iter_mut() {
+    for (entity, mut action_state, input_map, filter_state) in query.iter_mut() {
         let input_streams = InputStreams {
             gamepad_buttons,
             gamepad_button_axes,
@@ -129,9 +254,84 @@ pub fn update_action_state<A: Actionlike>(
             mouse_wheel,
             mouse_motion,
             associated_gamepad: input_map.gamepad(),
+            press_times,
+            mouse_motion_normalization: input_map.mouse_motion_normalization(),
+            frame_time: delta,
         };
 
-        action_state.update(input_map.which_pressed(&input_streams, *clash_strategy));
+        let action_data = match filter_state {
+            Some(mut filter_state) => input_map.which_pressed_filtered(
+                &input_streams,
+                *clash_strategy,
+                &mut filter_state,
+                delta,
+            ),
+            // Filters start from zero on the first frame, and are stored for the following frames
+            None if input_map.has_filters() => {
+                let mut filter_state = AxisFilterState::<A>::default();
+                let action_data = input_map.which_pressed_filtered(
+                    &input_streams,
+                    *clash_strategy,
+                    &mut filter_state,
+                    delta,
+                );
+                commands.add(move |world: &mut World| {
+                    // The entity may have been despawned or given an `AxisFilterState` in the meantime
+                    if let Some(mut entity) = world.get_entity_mut(entity) {
+                        if !entity.contains::<AxisFilterState<A>>() {
+                            entity.insert(filter_state);
+                        }
+                    }
+                });
+                action_data
+            }
+            None => input_map.which_pressed(&input_streams, *clash_strategy),
+        };
+        action_state.update_held_triggers(input_map.held_triggers(&input_streams, *clash_strategy));
+        apply_action_data(
+            &mut action_state,
+            action_data,
+            Some(entity),
+            buffered,
+            &mut action_events,
+        );
+    }
+}
+
+/// Updates the `action_state` of the `entity`, if any, sending [`ActionEvent`]s if they are enabled
+///
+/// If `buffered` is `true`, [`ActionState::update_buffered`] is used, as actions are ticked once per fixed timestep.
+fn apply_action_data<A: Actionlike>(
+    action_state: &mut ActionState<A>,
+    action_data: Vec<ActionData>,
+    entity: Option<Entity>,
+    buffered: bool,
+    action_events: &mut Option<ResMut<Events<ActionEvent<A>>>>,
+) {
+    let send_event = |event| {
+        if let Some(action_events) = action_events {
+            action_events.send(event);
+        }
+    };
+
+    if buffered {
+        action_state.update_buffered(action_data, entity, send_event);
+    } else {
+        action_state.update_with_events(action_data, entity, send_event);
+    }
+}
+
+/// Runs the [`ActionHandlers`] of each entity with an [`ActionState`]
+///
+/// Entities are visited one at a time, running each of their handlers in the order they were registered.
+pub fn run_action_handlers<A: Actionlike>(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, (With<ActionHandlers<A>>, With<ActionState<A>>)>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        ActionHandlers::<A>::run(entity, world);
     }
 }
 
@@ -274,3 +474,33 @@ pub(super) fn run_if_enabled<A: Actionlike>(toggle_actions: Res<ToggleActions<A>
         ShouldRun::No
     }
 }
+
+/// Creates a system that enables [`ToggleActions<A>`] while the [`State<S>`] is `active_state`, and disables it otherwise
+///
+/// This is added by [`InputManagerPlugin::active_in_state`](crate::plugin::InputManagerPlugin::active_in_state).
+/// [`ToggleActions<A>`] is only modified when it needs to change, so that [`release_on_disable`] releases actions once.
+/// If the [`State<S>`] resource does not exist, [`ToggleActions<A>`] is left unchanged.
+pub fn toggle_actions_in_state<A: Actionlike, S: StateData>(
+    active_state: S,
+) -> impl FnMut(Option<Res<State<S>>>, ResMut<ToggleActions<A>>) + Send + Sync + 'static {
+    move |state: Option<Res<State<S>>>, mut toggle_actions: ResMut<ToggleActions<A>>| {
+        if let Some(state) = state {
+            let enabled = *state.current() == active_state;
+            if toggle_actions.enabled != enabled {
+                toggle_actions.enabled = enabled;
+            }
+        }
+    }
+}
+
+/// Returns [`ShouldRun::No`] if [`ToggleActions<A>`] is disabled, and the result of the piped run criteria otherwise
+pub(super) fn and_if_enabled<A: Actionlike>(
+    In(should_run): In<ShouldRun>,
+    toggle_actions: Res<ToggleActions<A>>,
+) -> ShouldRun {
+    if toggle_actions.enabled {
+        should_run
+    } else {
+        ShouldRun::No
+    }
+}
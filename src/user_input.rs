@@ -2,11 +2,12 @@
 
 use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode, mouse::MouseButton};
 
-use bevy::utils::HashSet;
-use petitset::PetitSet;
+use bevy::reflect::{FromReflect, Reflect};
+use bevy::utils::{Duration, HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::axislike::VirtualAxis;
+use crate::input_set::InputSet;
 use crate::{
     axislike::{AxisType, DualAxis, SingleAxis, VirtualDPad},
     buttonlike::{MouseMotionDirection, MouseWheelDirection},
@@ -21,9 +22,9 @@ pub enum UserInput {
     Single(InputKind),
     /// A combination of buttons, pressed simultaneously
     ///
-    /// Up to 8 (!!) buttons can be chorded together at once.
+    /// Any number of buttons can be chorded together; the first 8 are stored inline.
     /// Chords are considered to belong to all of the [InputMode]s of their constituent buttons.
-    Chord(PetitSet<InputKind, 8>),
+    Chord(InputSet<InputKind, 8>),
     /// A virtual DPad that you can get an [`AxisPair`] from
     VirtualDPad(VirtualDPad),
     /// A virtual axis that you can get a [`SingleAxis`] from
@@ -37,7 +38,7 @@ impl UserInput {
     pub fn modified(modifier: Modifier, input: impl Into<InputKind>) -> UserInput {
         let modifier: InputKind = modifier.into();
         let input: InputKind = input.into();
-        let mut set: PetitSet<InputKind, 8> = PetitSet::default();
+        let mut set: InputSet<InputKind, 8> = InputSet::default();
         set.insert(modifier);
         set.insert(input);
 
@@ -49,9 +50,9 @@ impl UserInput {
     /// If `inputs` has a length of 1, a [`UserInput::Single`] variant will be returned instead.
     pub fn chord(inputs: impl IntoIterator<Item = impl Into<InputKind>>) -> Self {
         // We can't just check the length unless we add an ExactSizeIterator bound :(
-        let mut length: u8 = 0;
+        let mut length: usize = 0;
 
-        let mut set: PetitSet<InputKind, 8> = PetitSet::default();
+        let mut set: InputSet<InputKind, 8> = InputSet::default();
         for button in inputs {
             length += 1;
             set.insert(button.into());
@@ -144,6 +145,37 @@ impl UserInput {
         }
     }
 
+    /// Returns the [`InputDevice`]s that the raw inputs of this [`UserInput`] come from
+    #[must_use]
+    pub fn devices(&self) -> HashSet<InputDevice> {
+        let raw_inputs = self.raw_inputs();
+        let mut devices = HashSet::default();
+
+        if !raw_inputs.keycodes.is_empty() {
+            devices.insert(InputDevice::Keyboard);
+        }
+
+        if !raw_inputs.mouse_buttons.is_empty()
+            || !raw_inputs.mouse_wheel.is_empty()
+            || !raw_inputs.mouse_motion.is_empty()
+        {
+            devices.insert(InputDevice::Mouse);
+        }
+
+        if !raw_inputs.gamepad_buttons.is_empty() {
+            devices.insert(InputDevice::Gamepad);
+        }
+
+        for (axis_type, _) in raw_inputs.axis_data {
+            devices.insert(match axis_type {
+                AxisType::Gamepad(_) => InputDevice::Gamepad,
+                AxisType::MouseWheel(_) | AxisType::MouseMotion(_) => InputDevice::Mouse,
+            });
+        }
+
+        devices
+    }
+
     /// Returns the raw inputs that make up this [`UserInput`]
     pub fn raw_inputs(&self) -> RawInputs {
         let mut raw_inputs = RawInputs::default();
@@ -439,6 +471,146 @@ impl Modifier {
     }
 }
 
+/// Constraints on when the buttons of a [`UserInput::Chord`] must be pressed
+///
+/// By default, chords are pressed whenever all of their buttons are held, no matter when each button was pressed.
+/// This means a chord like `Shift + E` fires when a player holding `Shift` to sprint presses `E` to interact.
+/// Set these constraints on an action using [`InputMap::set_chord_timing`](crate::input_map::InputMap::set_chord_timing)
+/// to require that its chords are pressed deliberately.
+///
+/// Only keys and buttons have their press times recorded, by the [`ButtonPressTimes`](crate::input_streams::ButtonPressTimes) resource.
+/// Other inputs in the chord, such as axes, are not constrained.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::keyboard::KeyCode;
+/// use bevy::utils::Duration;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::user_input::ChordTiming;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug)]
+/// enum Action {
+///     QuickSave,
+/// }
+///
+/// let mut input_map = InputMap::default();
+/// input_map
+///     .insert_modified(Modifier::Control, KeyCode::S, Action::QuickSave)
+///     // `Control` must be pressed first, and `S` soon after
+///     .set_chord_timing(
+///         Action::QuickSave,
+///         ChordTiming::within(Duration::from_millis(500)).in_order(),
+///     );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChordTiming {
+    /// The longest time allowed between the first and last presses of the buttons in the chord, if limited
+    pub window: Option<Duration>,
+    /// Must the buttons be pressed in the order they were added to the chord?
+    ///
+    /// Buttons that are pressed in the same frame are considered to be in order.
+    pub ordered: bool,
+}
+
+impl ChordTiming {
+    /// Requires that all buttons in the chord are pressed within `window` of each other
+    #[must_use]
+    pub fn within(window: Duration) -> Self {
+        ChordTiming {
+            window: Some(window),
+            ordered: false,
+        }
+    }
+
+    /// Requires that the buttons in the chord are pressed in the order they were added to it
+    #[must_use]
+    pub fn in_order(self) -> Self {
+        ChordTiming {
+            ordered: true,
+            ..self
+        }
+    }
+}
+
+/// When does a binding in an [`InputMap`](crate::input_map::InputMap) press its action?
+///
+/// Set this for a binding using [`InputMap::insert_with_trigger`](crate::input_map::InputMap::insert_with_trigger).
+/// Bindings that are not pressed immediately do not press their action while they are held,
+/// so they do not clash with other actions bound to the same input.
+/// Instead, [`ActionState::update`](crate::action_state::ActionState::update) presses the action once they are released,
+/// unless the action was [consumed](crate::action_state::ActionState::consume) while they were held.
+/// This allows "tap to reload, hold to open the weapon wheel" on a single button:
+/// the weapon wheel consumes the reload action once the button has been held for long enough.
+///
+/// The action is just pressed in the tick after the binding is released, and released by the following update.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect, FromReflect,
+)]
+pub enum TriggerMode {
+    /// The action is pressed while the binding is pressed
+    #[default]
+    Press,
+    /// The action is pressed once the binding is released
+    Release,
+    /// The action is pressed once the binding is released,
+    /// if it was held for at most `max_duration`, as measured by [`Timing::previous_duration`](crate::action_state::Timing::previous_duration)
+    Click {
+        /// The longest the binding can be held for the release to count as a click
+        max_duration: Duration,
+    },
+}
+
+/// A kind of physical device that inputs come from
+///
+/// Used by [`AggregationPolicy::MostRecentDevice`] to find the device that the player is currently using.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputDevice {
+    /// A keyboard
+    Keyboard,
+    /// A mouse, including its buttons, wheel and motion
+    Mouse,
+    /// A gamepad, including its buttons and axes
+    Gamepad,
+}
+
+/// How the `value` and `axis_pair` of each binding are combined when several bindings of an action are pressed
+///
+/// Set this for an action using [`InputMap::set_aggregation`](crate::input_map::InputMap::set_aggregation).
+/// The action is pressed if any of its bindings are pressed, no matter which policy is used.
+///
+/// The `value` and `axis_pair` of a binding are zero unless it presses the action,
+/// or its [`AxisFilter`](crate::axis_filters::AxisFilter)s are still settling,
+/// and the `value` and `axis_pair` of the action are always taken from the same bindings.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect, FromReflect,
+)]
+pub enum AggregationPolicy {
+    /// The values and axis pairs of all bindings are added together,
+    /// using [`DualAxisData::merged_with`](crate::axislike::DualAxisData::merged_with) for axis pairs
+    ///
+    /// This may result in values outside of the expected range, such as a `value` of `2.0`
+    /// when both a key and a gamepad button are pressed.
+    #[default]
+    Sum,
+    /// Like [`AggregationPolicy::Sum`], but the `value` is clamped to `-1.0..=1.0`
+    /// and the length of the `axis_pair` is clamped to `1.0`
+    SumClamped,
+    /// The `value` and `axis_pair` of the binding with the largest magnitude are used
+    ///
+    /// The magnitude of a binding is the length of its `axis_pair`, or the absolute `value` of bindings without one.
+    MaxMagnitude,
+    /// The `value` and `axis_pair` of the first pressed binding are used, in the order the bindings were inserted
+    ///
+    /// While no binding is pressed, the bindings are combined as with [`AggregationPolicy::Sum`].
+    FirstBinding,
+    /// The `value` and `axis_pair` of the pressed binding whose [`InputDevice`] was used most recently are used
+    ///
+    /// This allows players to switch between a keyboard and a gamepad without the two interfering with each other.
+    /// Device use is recorded in the [`ButtonPressTimes`](crate::input_streams::ButtonPressTimes) resource.
+    /// Ties are broken, and bindings are combined while none are pressed, in the same way as [`AggregationPolicy::FirstBinding`].
+    MostRecentDevice,
+}
+
 /// The basic input events that make up a [`UserInput`].
 ///
 /// Obtained by calling [`UserInput::raw_inputs()`].
@@ -0,0 +1,441 @@
+//! Versioned serialization of [`InputMap`], which keeps saved bindings working as your actions change
+//!
+//! Renaming, reordering or removing the variants of your [`Actionlike`] enum
+//! can cause saved [`InputMap`]s to fail to load, or to bind inputs to the wrong actions.
+//! To avoid this, implement [`VersionedActionlike`] for your action type
+//! and save your bindings as a [`VersionedInputMap`] rather than an [`InputMap`].
+//!
+//! The serialized form of a [`VersionedInputMap`] is an [`InputMap`] with an additional `version` field.
+//! Per-action settings, such as [`ChordTiming`](crate::user_input::ChordTiming) constraints, are migrated along with the bindings.
+//! Files without this field (such as those saved from a plain [`InputMap`]) are treated as version 0.
+//! When loading, each action is migrated from the saved version to [`VersionedActionlike::VERSION`],
+//! and the bindings of any actions that no longer exist are skipped with a warning, rather than failing.
+//! Bindings that are rejected by [`InputMap::try_insert`], such as those that do not match the [`ActionKind`](crate::ActionKind) of their action,
+//! are skipped in the same way.
+//!
+//! # Example
+//! ```rust
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::versioning::{ActionMigration, VersionedActionlike};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Actionlike, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+//! enum Action {
+//!     // Called `Run` in version 0
+//!     Sprint,
+//!     Jump,
+//! }
+//!
+//! impl VersionedActionlike for Action {
+//!     const VERSION: u32 = 2;
+//!
+//!     fn migrate(from_version: u32, action: &str) -> ActionMigration {
+//!         match (from_version, action) {
+//!             (0, "Run") => ActionMigration::Rename("Sprint"),
+//!             // Crouching was removed in version 2
+//!             (1, "Crouch") => ActionMigration::Drop,
+//!             _ => ActionMigration::Keep,
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::axis_filters::AxisFilter;
+use crate::axislike::MouseMotionNormalization;
+use crate::errors::InputMapError;
+use crate::input_map::{DeserializedSettings, InputMap};
+use crate::user_input::{AggregationPolicy, ChordTiming, TriggerMode, UserInput};
+use crate::Actionlike;
+
+use bevy::log::warn;
+use core::hash::Hash;
+use serde::de::{DeserializeOwned, Error, IntoDeserializer, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+/// An [`Actionlike`] type whose serialized form is versioned, allowing saved bindings to be migrated as it changes
+///
+/// See the [module-level documentation](crate::versioning) for an example.
+pub trait VersionedActionlike: Actionlike {
+    /// The current version of the action type
+    ///
+    /// Increase this whenever you rename or remove an action,
+    /// and describe the changes in [`VersionedActionlike::migrate`].
+    const VERSION: u32;
+
+    /// Describes what happened to the `action` (as serialized) when moving from `from_version` to `from_version + 1`
+    ///
+    /// When loading a [`VersionedInputMap`], this is called once for each version between the saved version and [`VersionedActionlike::VERSION`],
+    /// passing in the result of the previous migration.
+    /// By default, all actions are kept as they are.
+    fn migrate(from_version: u32, action: &str) -> ActionMigration {
+        let _ = (from_version, action);
+        ActionMigration::Keep
+    }
+}
+
+/// A change made to a single action between two versions of a [`VersionedActionlike`] type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionMigration {
+    /// The action was unchanged
+    Keep,
+    /// The action was renamed; its bindings are moved to the action with this name
+    Rename(&'static str),
+    /// The action was removed; its bindings are discarded
+    Drop,
+}
+
+/// Migrates the serialized name of an action saved in `version` to the current version of `A`
+///
+/// Returns [`None`] if the action was dropped along the way.
+fn migrate_action<A: VersionedActionlike>(version: u32, action: &str) -> Option<String> {
+    let mut action = action.to_string();
+    for from_version in version..A::VERSION {
+        match A::migrate(from_version, &action) {
+            ActionMigration::Keep => (),
+            ActionMigration::Rename(new_name) => action = new_name.to_string(),
+            ActionMigration::Drop => return None,
+        }
+    }
+    Some(action)
+}
+
+/// An [`InputMap`] that is serialized along with the version of its action type
+///
+/// Deserializing this type migrates older bindings to the current version of `A`,
+/// and skips the bindings of unknown actions with a warning.
+/// See the [module-level documentation](crate::versioning) for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedInputMap<A: Actionlike>(pub InputMap<A>);
+
+impl<A: Actionlike> From<InputMap<A>> for VersionedInputMap<A> {
+    fn from(input_map: InputMap<A>) -> Self {
+        VersionedInputMap(input_map)
+    }
+}
+
+impl<A: Actionlike> From<VersionedInputMap<A>> for InputMap<A> {
+    fn from(versioned_input_map: VersionedInputMap<A>) -> Self {
+        versioned_input_map.0
+    }
+}
+
+impl<A> Serialize for VersionedInputMap<A>
+where
+    A: VersionedActionlike + Serialize + Eq + Hash + Ord,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut input_map =
+            serializer.serialize_struct("InputMap", 2 + InputMap::<A>::SERIALIZED_SETTINGS_LEN)?;
+        input_map.serialize_field("version", &A::VERSION)?;
+        input_map.serialize_field(
+            "map",
+            &self
+                .0
+                .iter()
+                .map(|(set, action)| (action, set.iter().collect()))
+                .collect::<BTreeMap<A, Vec<&UserInput>>>(),
+        )?;
+        self.0.serialize_settings(&mut input_map)?;
+        input_map.end()
+    }
+}
+
+impl<'de, A> Deserialize<'de> for VersionedInputMap<A>
+where
+    A: VersionedActionlike + DeserializeOwned + Eq + Hash,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// The serialized name of an action, which may no longer exist
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct ActionName(String);
+
+        impl<'de> Deserialize<'de> for ActionName {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct ActionNameVisitor;
+
+                impl<'de> Visitor<'de> for ActionNameVisitor {
+                    type Value = ActionName;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(formatter, "the name of an `Actionlike` variant")
+                    }
+
+                    fn visit_str<E: Error>(self, name: &str) -> Result<Self::Value, E> {
+                        Ok(ActionName(name.to_string()))
+                    }
+                }
+
+                // Unit variants are deserialized from identifiers
+                deserializer.deserialize_identifier(ActionNameVisitor)
+            }
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename = "InputMap")]
+        struct RawInputMap {
+            #[serde(default)]
+            version: u32,
+            map: BTreeMap<ActionName, Vec<UserInput>>,
+            #[serde(default)]
+            chord_timing: HashMap<ActionName, ChordTiming>,
+            #[serde(default)]
+            aggregation: HashMap<ActionName, AggregationPolicy>,
+            #[serde(default)]
+            triggers: HashMap<ActionName, Vec<(UserInput, TriggerMode)>>,
+            #[serde(default)]
+            filters: HashMap<ActionName, Vec<(UserInput, Vec<AxisFilter>)>>,
+            #[serde(default)]
+            mouse_motion_normalization: MouseMotionNormalization,
+        }
+
+        /// Deserializes the current `name` of an action, or returns [`None`] if there is no such action
+        fn deserialize_action<A: DeserializeOwned, E: Error>(name: &str) -> Option<A> {
+            let action_deserializer: serde::de::value::StrDeserializer<E> =
+                name.into_deserializer();
+            A::deserialize(action_deserializer).ok()
+        }
+
+        let raw_input_map = RawInputMap::deserialize(deserializer)?;
+        if raw_input_map.version > A::VERSION {
+            return Err(D::Error::custom(format!(
+                "the input map was saved with version {}, but the current version is {}",
+                raw_input_map.version,
+                A::VERSION
+            )));
+        }
+
+        let version = raw_input_map.version;
+        let mut input_map = InputMap::default();
+        for (ActionName(saved_name), inputs) in raw_input_map.map {
+            // Dropped actions are skipped silently
+            let Some(name) = migrate_action::<A>(version, &saved_name) else {
+                continue;
+            };
+
+            match deserialize_action::<A, D::Error>(&name) {
+                Some(action) => {
+                    for input in inputs {
+                        match input_map.try_insert(input, action.clone()) {
+                            Ok(_) | Err(InputMapError::DuplicateBinding { .. }) => (),
+                            Err(err) => warn!("Skipping binding for action `{saved_name}`: {err}"),
+                        }
+                    }
+                }
+                None => warn!(
+                    "Skipping {} bindings for unknown action `{saved_name}`",
+                    inputs.len()
+                ),
+            }
+        }
+
+        // The settings of unknown actions are skipped along with their bindings
+        let settings = DeserializedSettings {
+            chord_timing: raw_input_map.chord_timing,
+            aggregation: raw_input_map.aggregation,
+            triggers: raw_input_map.triggers,
+            filters: raw_input_map.filters,
+            mouse_motion_normalization: raw_input_map.mouse_motion_normalization,
+        };
+        settings.apply(&mut input_map, |ActionName(saved_name)| {
+            let name = migrate_action::<A>(version, &saved_name)?;
+            deserialize_action::<A, D::Error>(&name)
+        });
+
+        Ok(VersionedInputMap(input_map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::input::keyboard::KeyCode;
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
+
+    #[derive(
+        Actionlike, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug,
+    )]
+    enum Action {
+        Sprint,
+        Jump,
+    }
+
+    impl VersionedActionlike for Action {
+        const VERSION: u32 = 2;
+
+        fn migrate(from_version: u32, action: &str) -> ActionMigration {
+            match (from_version, action) {
+                (0, "Run") => ActionMigration::Rename("Dash"),
+                (1, "Dash") => ActionMigration::Rename("Sprint"),
+                (1, "Crouch") => ActionMigration::Drop,
+                _ => ActionMigration::Keep,
+            }
+        }
+    }
+
+    /// The tokens of a serialized `Keyboard` binding
+    fn key_tokens(variant: &'static str) -> [Token; 3] {
+        [
+            Token::NewtypeVariant {
+                name: "UserInput",
+                variant: "Single",
+            },
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant,
+            },
+        ]
+    }
+
+    /// The tokens of the settings of an input map that are left at their defaults
+    fn default_settings_tokens() -> [Token; 14] {
+        [
+            Token::Str("chord_timing"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("aggregation"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("triggers"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("filters"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("mouse_motion_normalization"),
+            Token::UnitVariant {
+                name: "MouseMotionNormalization",
+                variant: "Raw",
+            },
+        ]
+    }
+
+    /// The tokens of a serialized input map, with one binding per action
+    fn input_map_tokens(
+        version: Option<u32>,
+        bindings: &[(&'static str, &'static str)],
+    ) -> Vec<Token> {
+        let mut tokens = vec![Token::Struct {
+            name: "InputMap",
+            len: 7,
+        }];
+        if let Some(version) = version {
+            tokens.extend([Token::Str("version"), Token::U32(version)]);
+        }
+        tokens.extend([
+            Token::Str("map"),
+            Token::Map {
+                len: Some(bindings.len()),
+            },
+        ]);
+        for (action, key) in bindings {
+            tokens.extend([Token::Str(action), Token::Seq { len: Some(1) }]);
+            tokens.extend(key_tokens(key));
+            tokens.push(Token::SeqEnd);
+        }
+        tokens.extend([Token::MapEnd, Token::StructEnd]);
+        tokens
+    }
+
+    #[test]
+    fn current_version() {
+        let input_map = VersionedInputMap(InputMap::new([
+            (KeyCode::LShift, Action::Sprint),
+            (KeyCode::Space, Action::Jump),
+        ]));
+
+        let mut tokens = input_map_tokens(Some(2), &[("Sprint", "LShift"), ("Jump", "Space")]);
+        let struct_end = tokens.len() - 1;
+        tokens.splice(struct_end..struct_end, default_settings_tokens());
+        assert_de_tokens(&input_map, &tokens);
+
+        // Actions are serialized as unit variants
+        for token in tokens.iter_mut() {
+            if let Token::Str(action @ ("Sprint" | "Jump")) = token {
+                *token = Token::UnitVariant {
+                    name: "Action",
+                    variant: action,
+                };
+            }
+        }
+        assert_ser_tokens(&input_map, &tokens);
+    }
+
+    #[test]
+    fn migration() {
+        // Unversioned input maps are treated as version 0
+        let expected = VersionedInputMap(InputMap::new([
+            (KeyCode::LShift, Action::Sprint),
+            (KeyCode::Space, Action::Jump),
+        ]));
+        assert_de_tokens(
+            &expected,
+            &input_map_tokens(None, &[("Run", "LShift"), ("Jump", "Space")]),
+        );
+
+        // Dropped and unknown actions are skipped
+        assert_de_tokens(
+            &expected,
+            &input_map_tokens(
+                Some(1),
+                &[
+                    ("Dash", "LShift"),
+                    ("Jump", "Space"),
+                    ("Crouch", "LControl"),
+                    ("Fly", "F"),
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn newer_version() {
+        assert_de_tokens_error::<VersionedInputMap<Action>>(
+            &input_map_tokens(Some(3), &[("Jump", "Space")]),
+            "the input map was saved with version 3, but the current version is 2",
+        );
+    }
+
+    #[test]
+    fn migrating_settings() {
+        use crate::user_input::{AggregationPolicy, TriggerMode};
+
+        let mut input_map = InputMap::new([
+            (KeyCode::LShift, Action::Sprint),
+            (KeyCode::Space, Action::Jump),
+        ]);
+        input_map
+            .set_aggregation(Action::Sprint, AggregationPolicy::MaxMagnitude)
+            .set_trigger(Action::Jump, KeyCode::Space, TriggerMode::Release);
+        let versioned_input_map = VersionedInputMap(input_map);
+
+        let serialized = ron::to_string(&versioned_input_map).unwrap();
+        let deserialized: VersionedInputMap<Action> = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, versioned_input_map);
+
+        // Settings are migrated along with the bindings of their action
+        let deserialized: VersionedInputMap<Action> = ron::from_str(
+            "(version: 0, map: {Run: [Single(Keyboard(LShift))], Jump: [Single(Keyboard(Space))]}, \
+            aggregation: {Run: MaxMagnitude, Crouch: FirstBinding}, \
+            triggers: {Jump: [(Single(Keyboard(Space)), Release)]})",
+        )
+        .unwrap();
+        assert_eq!(deserialized, versioned_input_map);
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::ecs::schedule::ShouldRun;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::action_handlers::{ActionHandlers, HandlerResponse, HandlerTrigger};
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Interact,
+}
+
+#[derive(Component, Default)]
+struct Door {
+    times_opened: u32,
+}
+
+/// How many times systems in `CoreStage::Update` saw `Action::Interact` as just pressed
+#[derive(Resource, Default)]
+struct SeenBySystems(u32);
+
+fn open_door(In(entity): In<Entity>, mut doors: Query<&mut Door>) -> HandlerResponse {
+    doors.get_mut(entity).unwrap().times_opened += 1;
+    HandlerResponse::Consume
+}
+
+fn observe(query: Query<&ActionState<Action>>, mut seen: ResMut<SeenBySystems>) {
+    for action_state in query.iter() {
+        seen.0 += action_state.just_pressed(Action::Interact) as u32;
+    }
+}
+
+fn spawn_door(app: &mut App) -> Entity {
+    let mut handlers = ActionHandlers::<Action>::default();
+    handlers.insert_system(HandlerTrigger::JustPressed, Action::Interact, open_door);
+
+    app.world
+        .spawn((
+            Door::default(),
+            InputManagerBundle {
+                input_map: InputMap::new([(KeyCode::E, Action::Interact)]),
+                ..default()
+            },
+            handlers,
+        ))
+        .id()
+}
+
+fn times_opened(app: &App, door: Entity) -> u32 {
+    app.world.get::<Door>(door).unwrap().times_opened
+}
+
+#[test]
+fn handlers_consume_before_update() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default().with_action_handlers())
+        .init_resource::<SeenBySystems>()
+        .add_system(observe);
+    let door = spawn_door(&mut app);
+
+    app.send_input(KeyCode::E);
+    app.update();
+    assert_eq!(times_opened(&app, door), 1);
+    assert_eq!(app.world.resource::<SeenBySystems>().0, 0);
+
+    // Consumed actions are not pressed again while the key is held
+    app.update();
+    assert_eq!(times_opened(&app, door), 1);
+
+    app.release_input(KeyCode::E);
+    app.update();
+    app.send_input(KeyCode::E);
+    app.update();
+    assert_eq!(times_opened(&app, door), 2);
+    assert_eq!(app.world.resource::<SeenBySystems>().0, 0);
+}
+
+#[derive(StageLabel)]
+struct FixedStage;
+
+/// The number of fixed steps to run in the next frame
+#[derive(Resource, Default)]
+struct PendingSteps(u32);
+
+fn run_pending_steps(mut pending_steps: ResMut<PendingSteps>) -> ShouldRun {
+    if pending_steps.0 > 0 {
+        pending_steps.0 -= 1;
+        ShouldRun::YesAndCheckAgain
+    } else {
+        ShouldRun::No
+    }
+}
+
+#[test]
+fn handlers_run_once_per_fixed_step() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_stage_after(
+            CoreStage::Update,
+            FixedStage,
+            SystemStage::parallel().with_run_criteria(run_pending_steps),
+        )
+        .add_plugin(
+            InputManagerPlugin::<Action>::fixed_timestep(FixedStage, Duration::from_millis(20))
+                .with_action_handlers(),
+        )
+        .init_resource::<PendingSteps>();
+    let door = spawn_door(&mut app);
+
+    // Nothing happens until a fixed step runs
+    app.send_input(KeyCode::E);
+    app.update();
+    app.update();
+    assert_eq!(times_opened(&app, door), 0);
+
+    app.world.resource_mut::<PendingSteps>().0 = 3;
+    app.update();
+    assert_eq!(times_opened(&app, door), 1);
+}
@@ -30,6 +30,21 @@ enum NamedFieldVariantsAction {
     Jump,
 }
 
+#[test]
+fn variant_counts() {
+    assert_eq!(UnitAction::N_VARIANTS, 0);
+    assert_eq!(UnitAction::variants().count(), 0);
+
+    assert_eq!(OneAction::N_VARIANTS, 1);
+    assert_eq!(OneAction::variants().collect::<Vec<_>>(), [OneAction::Jump]);
+
+    assert_eq!(UnnamedFieldVariantsAction::N_VARIANTS, 2);
+    assert_eq!(
+        UnnamedFieldVariantsAction::Jump(3).index(),
+        UnnamedFieldVariantsAction::Jump(0).index()
+    );
+}
+
 #[test]
 fn in_order_iteration() {
     let constructed_vec = vec![SimpleAction::Zero, SimpleAction::One, SimpleAction::Two];
@@ -55,3 +70,253 @@ fn index() {
     assert_eq!(SimpleAction::One.index(), 1);
     assert_eq!(SimpleAction::Two.index(), 2);
 }
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum BoundAction {
+    #[actionlike(key = "Space", gamepad = "South")]
+    Jump,
+    #[actionlike(key = "LShift")]
+    #[actionlike(key = "RShift", mouse = "Right")]
+    Run,
+    Emote,
+}
+
+#[test]
+fn default_bindings() {
+    use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode, mouse::MouseButton};
+    use leafwing_input_manager::input_map::InputMap;
+    use leafwing_input_manager::user_input::UserInput;
+
+    assert!(SimpleAction::default_bindings().is_empty());
+
+    let mut expected = InputMap::default();
+    expected
+        .insert(KeyCode::Space, BoundAction::Jump)
+        .insert(GamepadButtonType::South, BoundAction::Jump)
+        .insert(KeyCode::LShift, BoundAction::Run)
+        .insert(KeyCode::RShift, BoundAction::Run)
+        .insert(MouseButton::Right, BoundAction::Run);
+    assert_eq!(InputMap::<BoundAction>::default_bindings(), expected);
+
+    assert_eq!(
+        BoundAction::default_bindings()[0],
+        (UserInput::from(KeyCode::Space), BoundAction::Jump)
+    );
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum DescribedAction {
+    #[actionlike(
+        name = "Jump!",
+        category = "Movement",
+        description = "Leap into the air"
+    )]
+    Jump,
+    #[actionlike(rebindable = false)]
+    Pause,
+}
+
+#[test]
+fn metadata() {
+    use leafwing_input_manager::ActionMetadata;
+
+    assert_eq!(
+        DescribedAction::Jump.metadata(),
+        ActionMetadata {
+            name: "Jump!",
+            category: Some("Movement"),
+            description: Some("Leap into the air"),
+            rebindable: true,
+        }
+    );
+
+    // Names default to the name of the variant
+    assert_eq!(
+        DescribedAction::Pause.metadata(),
+        ActionMetadata {
+            name: "Pause",
+            category: None,
+            description: None,
+            rebindable: false,
+        }
+    );
+
+    assert_eq!(NamedFieldVariantsAction::Jump.metadata().name, "Jump");
+    assert_eq!(
+        NamedFieldVariantsAction::Run { x: 1, y: 2 }.metadata().name,
+        "Run"
+    );
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum KindedAction {
+    #[actionlike(kind = "Button")]
+    Jump,
+    #[actionlike(kind = "DualAxis")]
+    Move,
+    Undeclared,
+}
+
+#[test]
+fn kind() {
+    use leafwing_input_manager::ActionKind;
+
+    assert_eq!(KindedAction::Jump.kind(), Some(ActionKind::Button));
+    assert_eq!(KindedAction::Move.kind(), Some(ActionKind::DualAxis));
+    assert_eq!(KindedAction::Undeclared.kind(), None);
+    assert_eq!(SimpleAction::Zero.kind(), None);
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum RangedAction {
+    Jump,
+    #[actionlike(range = 0..10, name = "Hotbar slot")]
+    Hotbar(u8),
+    #[actionlike(range = -1..=1)]
+    Lean(i32),
+    Crouch,
+}
+
+#[test]
+fn ranged_variants() {
+    assert_eq!(RangedAction::N_VARIANTS, 15);
+
+    assert_eq!(RangedAction::Jump.index(), 0);
+    assert_eq!(RangedAction::Hotbar(0).index(), 1);
+    assert_eq!(RangedAction::Hotbar(9).index(), 10);
+    assert_eq!(RangedAction::Lean(-1).index(), 11);
+    assert_eq!(RangedAction::Lean(1).index(), 13);
+    assert_eq!(RangedAction::Crouch.index(), 14);
+
+    let variants: Vec<RangedAction> = RangedAction::variants().collect();
+    assert_eq!(variants.len(), RangedAction::N_VARIANTS);
+    for (index, action) in variants.iter().enumerate() {
+        assert_eq!(action.index(), index);
+        assert_eq!(RangedAction::get_at(index), Some(*action));
+    }
+    assert_eq!(variants[4], RangedAction::Hotbar(3));
+    assert_eq!(variants[12], RangedAction::Lean(0));
+    assert_eq!(RangedAction::get_at(15), None);
+
+    let mut variants = RangedAction::variants();
+    assert_eq!(variants.len(), RangedAction::N_VARIANTS);
+    variants.nth(4);
+    assert_eq!(variants.len(), RangedAction::N_VARIANTS - 5);
+
+    assert_eq!(RangedAction::Hotbar(5).metadata().name, "Hotbar slot");
+}
+
+#[test]
+#[should_panic]
+fn ranged_variant_out_of_range() {
+    let _ = RangedAction::Hotbar(10).index();
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum FullRangeAction {
+    #[actionlike(range = 0..=255)]
+    Slot(u8),
+    #[actionlike(range = -128..=127)]
+    Offset(i8),
+}
+
+#[test]
+fn ranges_can_span_their_field_type() {
+    assert_eq!(FullRangeAction::N_VARIANTS, 512);
+    assert_eq!(FullRangeAction::Slot(255).index(), 255);
+    assert_eq!(
+        FullRangeAction::get_at(255),
+        Some(FullRangeAction::Slot(255))
+    );
+    assert_eq!(FullRangeAction::Offset(-128).index(), 256);
+    assert_eq!(
+        FullRangeAction::get_at(511),
+        Some(FullRangeAction::Offset(127))
+    );
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum MovementAction {
+    #[actionlike(key = "Space")]
+    Jump,
+    #[actionlike(kind = "DualAxis")]
+    Move,
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum CombatAction {
+    #[actionlike(category = "Abilities")]
+    Attack,
+    #[actionlike(range = 1..=3)]
+    Ability(u8),
+}
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+enum GameAction {
+    #[actionlike(nested)]
+    Movement(MovementAction),
+    #[actionlike(key = "Escape")]
+    Pause,
+    #[actionlike(nested, category = "Combat")]
+    Combat(CombatAction),
+    Emote,
+}
+
+#[test]
+fn nested_variants() {
+    use bevy::input::keyboard::KeyCode;
+    use leafwing_input_manager::user_input::UserInput;
+    use leafwing_input_manager::ActionKind;
+    use GameAction::*;
+
+    assert_eq!(GameAction::N_VARIANTS, 2 + 1 + 4 + 1);
+
+    let variants: Vec<GameAction> = GameAction::variants().collect();
+    assert_eq!(
+        variants,
+        vec![
+            Movement(MovementAction::Jump),
+            Movement(MovementAction::Move),
+            Pause,
+            Combat(CombatAction::Attack),
+            Combat(CombatAction::Ability(1)),
+            Combat(CombatAction::Ability(2)),
+            Combat(CombatAction::Ability(3)),
+            Emote,
+        ]
+    );
+    for (index, action) in variants.iter().enumerate() {
+        assert_eq!(action.index(), index);
+        assert_eq!(GameAction::get_at(index), Some(*action));
+    }
+    assert_eq!(GameAction::get_at(8), None);
+
+    assert_eq!(
+        Movement(MovementAction::Move).kind(),
+        Some(ActionKind::DualAxis)
+    );
+    assert_eq!(Pause.kind(), None);
+
+    let jump_metadata = Movement(MovementAction::Jump).metadata();
+    assert_eq!(jump_metadata.name, "Jump");
+    assert_eq!(jump_metadata.category, Some("Movement"));
+    assert_eq!(
+        Combat(CombatAction::Attack).metadata().category,
+        Some("Abilities")
+    );
+    assert_eq!(
+        Combat(CombatAction::Ability(2)).metadata().category,
+        Some("Combat")
+    );
+
+    assert_eq!(
+        GameAction::default_bindings(),
+        vec![
+            (UserInput::from(KeyCode::Escape), Pause),
+            (
+                UserInput::from(KeyCode::Space),
+                Movement(MovementAction::Jump)
+            ),
+        ]
+    );
+}
@@ -0,0 +1,195 @@
+use bevy::input::gamepad::{GamepadEventRaw, GamepadInfo};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::axislike::{DualAxisData, VirtualAxis};
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::{AggregationPolicy, TriggerMode};
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Steer,
+    Move,
+}
+
+fn test_app(policy: AggregationPolicy) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>();
+
+    let mut input_map = InputMap::default();
+    input_map
+        .insert(VirtualAxis::horizontal_arrow_keys(), Action::Steer)
+        .insert(
+            SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1),
+            Action::Steer,
+        )
+        .set_aggregation(Action::Steer, policy);
+    app.insert_resource(input_map);
+
+    // Gamepads must be registered for gamepad input mocking to work
+    app.world
+        .resource_mut::<Events<GamepadEventRaw>>()
+        .send(GamepadEventRaw {
+            gamepad: Gamepad { id: 1 },
+            event_type: GamepadEventType::Connected(GamepadInfo {
+                name: "TestController".into(),
+            }),
+        });
+    app.update();
+    app.update();
+
+    app
+}
+
+fn tilt_stick(app: &mut App, value: f32) {
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickX, value));
+}
+
+fn steering(app: &App) -> f32 {
+    app.world
+        .resource::<ActionState<Action>>()
+        .value(Action::Steer)
+}
+
+/// Steers using both the keyboard and the gamepad, returning the resulting value
+fn steer_with_both(policy: AggregationPolicy) -> f32 {
+    let mut app = test_app(policy);
+    app.send_input(KeyCode::Right);
+    tilt_stick(&mut app, 0.5);
+    app.update();
+    steering(&app)
+}
+
+#[test]
+fn sum() {
+    assert_eq!(steer_with_both(AggregationPolicy::Sum), 1.5);
+}
+
+#[test]
+fn sum_clamped() {
+    assert_eq!(steer_with_both(AggregationPolicy::SumClamped), 1.0);
+}
+
+#[test]
+fn max_magnitude() {
+    assert_eq!(steer_with_both(AggregationPolicy::MaxMagnitude), 1.0);
+
+    let mut app = test_app(AggregationPolicy::MaxMagnitude);
+    app.send_input(KeyCode::Right);
+    tilt_stick(&mut app, -0.5);
+    app.update();
+    assert_eq!(steering(&app), 1.0);
+}
+
+#[test]
+fn max_magnitude_uses_a_single_binding() {
+    let mut app = test_app(AggregationPolicy::MaxMagnitude);
+    let mut input_map = InputMap::default();
+    input_map
+        .insert(KeyCode::Space, Action::Move)
+        .insert(DualAxis::left_stick(), Action::Move)
+        .set_aggregation(Action::Move, AggregationPolicy::MaxMagnitude);
+    app.insert_resource(input_map);
+
+    // Only the stick has an axis pair, but the key has the largest magnitude
+    app.send_input(KeyCode::Space);
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.5,
+        0.0,
+    ));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.value(Action::Move), 1.0);
+    assert_eq!(action_state.axis_pair(Action::Move), None);
+
+    app.release_input(KeyCode::Space);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.value(Action::Move), 0.5);
+    assert_eq!(
+        action_state.axis_pair(Action::Move),
+        Some(DualAxisData::new(0.5, 0.0))
+    );
+}
+
+#[test]
+fn first_binding() {
+    assert_eq!(steer_with_both(AggregationPolicy::FirstBinding), 1.0);
+
+    // Later bindings are used while the first is released
+    let mut app = test_app(AggregationPolicy::FirstBinding);
+    tilt_stick(&mut app, 0.5);
+    app.update();
+    assert_eq!(steering(&app), 0.5);
+}
+
+#[test]
+fn most_recent_device() {
+    let mut app = test_app(AggregationPolicy::MostRecentDevice);
+    app.send_input(KeyCode::Right);
+    app.update();
+    assert_eq!(steering(&app), 1.0);
+
+    // Tilting the stick switches to the gamepad...
+    tilt_stick(&mut app, 0.5);
+    app.update();
+    assert_eq!(steering(&app), 0.5);
+
+    // ...and holding it still does not stop the keyboard from taking over again
+    app.release_input(KeyCode::Right);
+    app.update();
+    app.send_input(KeyCode::Left);
+    app.update();
+    assert_eq!(steering(&app), -1.0);
+}
+
+#[test]
+fn value_and_axis_pair_use_the_same_bindings() {
+    use AggregationPolicy::*;
+
+    for policy in [
+        Sum,
+        SumClamped,
+        MaxMagnitude,
+        FirstBinding,
+        MostRecentDevice,
+    ] {
+        let mut app = test_app(policy);
+        let mut input_map = InputMap::default();
+        input_map
+            .insert_with_trigger(
+                VirtualDPad::arrow_keys(),
+                Action::Move,
+                TriggerMode::Release,
+            )
+            .insert(VirtualDPad::wasd(), Action::Move)
+            .set_aggregation(Action::Move, policy);
+        app.insert_resource(input_map);
+
+        // The arrow keys only press the action once they are released, so they do not move it while held
+        app.send_input(KeyCode::Right);
+        app.update();
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(action_state.released(Action::Move), "{policy:?}");
+        assert_eq!(action_state.value(Action::Move), 0.0, "{policy:?}");
+        assert_eq!(
+            action_state.axis_pair(Action::Move),
+            Some(DualAxisData::new(0.0, 0.0)),
+            "{policy:?}"
+        );
+
+        app.send_input(KeyCode::W);
+        app.update();
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert_eq!(action_state.value(Action::Move), 1.0, "{policy:?}");
+        assert_eq!(
+            action_state.axis_pair(Action::Move),
+            Some(DualAxisData::new(0.0, 1.0)),
+            "{policy:?}"
+        );
+    }
+}
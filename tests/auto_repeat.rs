@@ -0,0 +1,112 @@
+use bevy::input::gamepad::{GamepadEventRaw, GamepadInfo};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+use leafwing_input_manager::action_state::RepeatSettings;
+use leafwing_input_manager::input_streams::InputStreams;
+use leafwing_input_manager::prelude::*;
+
+const FRAME: Duration = Duration::from_millis(10);
+const GAMEPAD: Gamepad = Gamepad { id: 1 };
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Down,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugin(InputPlugin);
+
+    // Gamepads must be registered for gamepad input mocking to work
+    app.world
+        .resource_mut::<Events<GamepadEventRaw>>()
+        .send(GamepadEventRaw {
+            gamepad: GAMEPAD,
+            event_type: GamepadEventType::Connected(GamepadInfo {
+                name: "TestController".into(),
+            }),
+        });
+    app.update();
+    app.update();
+    app
+}
+
+/// Holds the input down for `frames` frames of length [`FRAME`], counting how often the action was just pressed
+fn just_pressed_while_held(input: impl Into<UserInput>, frames: u32) -> u32 {
+    let mut app = test_app();
+    let input = input.into();
+    let mut input_map = InputMap::new([(input.clone(), Action::Down)]);
+    input_map.set_gamepad(GAMEPAD);
+
+    let mut action_state = ActionState::<Action>::default();
+    action_state.set_repeat(
+        Action::Down,
+        Some(RepeatSettings::new(
+            Duration::from_millis(300),
+            Duration::from_millis(80),
+        )),
+    );
+
+    app.send_input(input);
+    app.update();
+
+    let start = Instant::now();
+    let mut just_pressed = 0;
+    for frame in 0..frames {
+        let input_streams = InputStreams::from_world(&app.world, Some(GAMEPAD));
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+        just_pressed += action_state.just_pressed(Action::Down) as u32;
+
+        let previous = start + FRAME * frame;
+        action_state.tick(previous + FRAME, previous);
+    }
+
+    just_pressed
+}
+
+#[test]
+fn repeats_identically_for_each_input_kind() {
+    // Held for 700 ms: pressed at 0 ms, then repeated at 300, 380, 460, 540 and 620 ms
+    let expected = 6;
+
+    assert_eq!(just_pressed_while_held(KeyCode::Down, 70), expected);
+    assert_eq!(
+        just_pressed_while_held(GamepadButtonType::DPadDown, 70),
+        expected
+    );
+    assert_eq!(
+        just_pressed_while_held(
+            DualAxis::from_value(
+                GamepadAxisType::LeftStickX,
+                GamepadAxisType::LeftStickY,
+                0.0,
+                -1.0
+            ),
+            70
+        ),
+        expected
+    );
+}
+
+#[test]
+fn repeats_restart_after_release() {
+    let mut action_state = ActionState::<Action>::default();
+    action_state.set_repeat(
+        Action::Down,
+        Some(RepeatSettings::new(
+            Duration::from_millis(300),
+            Duration::from_millis(80),
+        )),
+    );
+
+    let start = Instant::now();
+    action_state.press(Action::Down);
+    action_state.tick(start, start);
+    action_state.tick(start + Duration::from_millis(400), start);
+    assert_eq!(action_state.repeats(Action::Down), 2);
+
+    action_state.release(Action::Down);
+    action_state.press(Action::Down);
+    assert_eq!(action_state.repeats(Action::Down), 0);
+}
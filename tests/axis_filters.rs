@@ -0,0 +1,162 @@
+use bevy::input::gamepad::{GamepadEventRaw, GamepadInfo};
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::axis_filters::{AxisFilter, AxisFilterState};
+use leafwing_input_manager::axislike::DualAxisData;
+use leafwing_input_manager::prelude::*;
+
+mod common;
+use common::TimedApp;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Turn,
+    Look,
+}
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn input_map(filter: AxisFilter) -> InputMap<Action> {
+    let mut input_map = InputMap::new([(KeyCode::Right, Action::Turn)]);
+    input_map.set_filters(Action::Turn, KeyCode::Right, [filter]);
+    input_map
+}
+
+/// Each following frame is run a [`FRAME`] after the last, using [`TimedApp::update_at`]
+fn test_app() -> TimedApp {
+    let mut app = TimedApp::new();
+    app.add_plugin(InputManagerPlugin::<Action>::default());
+    app.update_at(Duration::ZERO);
+    app
+}
+
+#[test]
+fn filters_are_applied_to_entities() {
+    let mut app = test_app();
+    let player = app
+        .world
+        .spawn((
+            InputManagerBundle {
+                input_map: input_map(AxisFilter::Acceleration { ramp: FRAME * 4 }),
+                ..default()
+            },
+            AxisFilterState::<Action>::default(),
+        ))
+        .id();
+
+    app.send_input(KeyCode::Right);
+    let mut values = Vec::new();
+    for i in 1..=5 {
+        app.update_at(FRAME * i);
+        let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+        values.push(action_state.value(Action::Turn));
+    }
+
+    assert_eq!(values, [0.25, 0.5, 0.75, 1.0, 1.0]);
+}
+
+#[test]
+fn filter_state_is_added_to_entities() {
+    let mut app = test_app();
+    let player = app
+        .world
+        .spawn(InputManagerBundle {
+            input_map: input_map(AxisFilter::Acceleration { ramp: FRAME * 4 }),
+            ..default()
+        })
+        .id();
+
+    app.send_input(KeyCode::Right);
+    let mut values = Vec::new();
+    for i in 1..=3 {
+        app.update_at(FRAME * i);
+        let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+        values.push(action_state.value(Action::Turn));
+    }
+
+    assert_eq!(values, [0.25, 0.5, 0.75]);
+    assert!(app.world.get::<AxisFilterState<Action>>(player).is_some());
+}
+
+#[test]
+fn filters_are_applied_to_resources() {
+    let mut app = test_app();
+    app.insert_resource(input_map(AxisFilter::Exponential { half_life: FRAME }))
+        .init_resource::<ActionState<Action>>();
+
+    app.send_input(KeyCode::Right);
+    app.update_at(FRAME * 1);
+    assert_eq!(
+        app.world
+            .resource::<ActionState<Action>>()
+            .value(Action::Turn),
+        0.5
+    );
+    app.update_at(FRAME * 2);
+    assert_eq!(
+        app.world
+            .resource::<ActionState<Action>>()
+            .value(Action::Turn),
+        0.75
+    );
+
+    // Filters do not change whether actions are pressed
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(Action::Turn));
+}
+
+#[test]
+fn released_sticks_settle_smoothly() {
+    let mut app = test_app();
+    let mut input_map = InputMap::new([(DualAxis::left_stick(), Action::Look)]);
+    input_map.set_filters(
+        Action::Look,
+        DualAxis::left_stick(),
+        [AxisFilter::Exponential { half_life: FRAME }],
+    );
+    app.insert_resource(input_map)
+        .init_resource::<ActionState<Action>>();
+
+    // Gamepads must be registered for gamepad input mocking to work
+    app.world
+        .resource_mut::<Events<GamepadEventRaw>>()
+        .send(GamepadEventRaw {
+            gamepad: Gamepad { id: 1 },
+            event_type: GamepadEventType::Connected(GamepadInfo {
+                name: "TestController".into(),
+            }),
+        });
+    app.update_at(FRAME * 1);
+
+    let stick = |x| {
+        DualAxis::from_value(
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+            x,
+            0.0,
+        )
+    };
+    app.send_input(stick(1.0));
+    app.update_at(FRAME * 2);
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.value(Action::Look), 0.5);
+    assert_eq!(
+        action_state.axis_pair(Action::Look),
+        Some(DualAxisData::new(0.5, 0.0))
+    );
+
+    // The action is released straight away, but its value and axis pair decay together
+    app.send_input(stick(0.0));
+    for (i, expected) in [(3, 0.25), (4, 0.125)] {
+        app.update_at(FRAME * i);
+        let action_state = app.world.resource::<ActionState<Action>>();
+        assert!(action_state.released(Action::Look));
+        assert_eq!(action_state.value(Action::Look), expected);
+        assert_eq!(
+            action_state.axis_pair(Action::Look),
+            Some(DualAxisData::new(expected, 0.0))
+        );
+    }
+}
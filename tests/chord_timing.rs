@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::ChordTiming;
+
+mod common;
+use common::TimedApp;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Sprint,
+    Interact,
+    Dodge,
+}
+
+/// Presses the buttons of a chord whose timing is constrained
+struct TestApp {
+    app: TimedApp,
+}
+
+impl TestApp {
+    fn new(timing: ChordTiming) -> Self {
+        let mut app = TimedApp::new();
+        app.add_plugin(InputManagerPlugin::<Action>::default())
+            .init_resource::<ActionState<Action>>();
+
+        let mut input_map = InputMap::new([
+            (KeyCode::LShift, Action::Sprint),
+            (KeyCode::E, Action::Interact),
+        ]);
+        input_map
+            .insert_chord([KeyCode::LShift, KeyCode::E], Action::Dodge)
+            .set_chord_timing(Action::Dodge, timing);
+        app.insert_resource(input_map);
+
+        TestApp { app }
+    }
+
+    /// Presses the `key`, `millis` milliseconds after the start of the test
+    fn press_at(&mut self, key: KeyCode, millis: u64) {
+        self.app.send_input(key);
+        self.app.update_at(Duration::from_millis(millis));
+    }
+
+    fn pressed(&self, action: Action) -> bool {
+        self.app
+            .world
+            .resource::<ActionState<Action>>()
+            .pressed(action)
+    }
+}
+
+#[test]
+fn chord_within_window() {
+    let mut app = TestApp::new(ChordTiming::within(Duration::from_millis(200)));
+    app.press_at(KeyCode::LShift, 0);
+    app.press_at(KeyCode::E, 150);
+
+    assert!(app.pressed(Action::Dodge));
+}
+
+#[test]
+fn chord_outside_window() {
+    let mut app = TestApp::new(ChordTiming::within(Duration::from_millis(200)));
+    app.press_at(KeyCode::LShift, 0);
+    app.press_at(KeyCode::E, 1000);
+
+    // The chord does not fire, so it no longer clashes with its buttons
+    assert!(!app.pressed(Action::Dodge));
+    assert!(app.pressed(Action::Sprint));
+    assert!(app.pressed(Action::Interact));
+}
+
+#[test]
+fn chord_in_order() {
+    let timing = ChordTiming::within(Duration::from_millis(200)).in_order();
+
+    let mut app = TestApp::new(timing);
+    app.press_at(KeyCode::LShift, 0);
+    app.press_at(KeyCode::E, 100);
+    assert!(app.pressed(Action::Dodge));
+
+    let mut app = TestApp::new(timing);
+    app.press_at(KeyCode::E, 0);
+    app.press_at(KeyCode::LShift, 100);
+    assert!(!app.pressed(Action::Dodge));
+}
+
+#[test]
+fn unconstrained_chord() {
+    let mut app = TestApp::new(ChordTiming::default());
+    app.press_at(KeyCode::E, 0);
+    app.press_at(KeyCode::LShift, 5000);
+
+    assert!(app.pressed(Action::Dodge));
+}
@@ -0,0 +1,50 @@
+//! Helpers shared by the integration tests
+
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::{Duration, Instant};
+use std::ops::{Deref, DerefMut};
+
+/// An [`App`] whose frames are advanced by hand, so that tests can time their inputs precisely
+///
+/// Its [`Time`] only advances when [`TimedApp::update_at`] is called.
+pub struct TimedApp {
+    app: App,
+    start: Instant,
+}
+
+impl TimedApp {
+    /// Creates an [`App`] with the [`InputPlugin`], whose clock starts now
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugin(InputPlugin).init_resource::<Time>();
+
+        TimedApp {
+            app,
+            start: Instant::now(),
+        }
+    }
+
+    /// Runs a frame `elapsed` after the start of the test
+    pub fn update_at(&mut self, elapsed: Duration) {
+        self.app
+            .world
+            .resource_mut::<Time>()
+            .update_with_instant(self.start + elapsed);
+        self.app.update();
+    }
+}
+
+impl Deref for TimedApp {
+    type Target = App;
+
+    fn deref(&self) -> &App {
+        &self.app
+    }
+}
+
+impl DerefMut for TimedApp {
+    fn deref_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::cooldown::{Charges, Cooldown, Cooldowns};
+use leafwing_input_manager::prelude::*;
+
+mod common;
+use common::TimedApp;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Ability {
+    Dash,
+}
+
+/// How many times the player has dashed
+#[derive(Component, Default)]
+struct Dashes(u32);
+
+fn dash(mut query: Query<(&ActionState<Ability>, &mut Cooldowns<Ability>, &mut Dashes)>) {
+    for (action_state, mut cooldowns, mut dashes) in query.iter_mut() {
+        if action_state.just_pressed(Ability::Dash)
+            && cooldowns.trigger_if_ready(Ability::Dash).is_ok()
+        {
+            dashes.0 += 1;
+        }
+    }
+}
+
+/// Taps the dash key of a player with [`Cooldowns`]
+struct TestApp {
+    app: TimedApp,
+    player: Entity,
+}
+
+impl TestApp {
+    fn new(cooldowns: Cooldowns<Ability>) -> Self {
+        let mut app = TimedApp::new();
+        app.add_plugin(InputManagerPlugin::<Ability>::default())
+            .add_system(dash);
+
+        let player = app
+            .world
+            .spawn((
+                InputManagerBundle {
+                    input_map: InputMap::new([(KeyCode::Space, Ability::Dash)]),
+                    ..default()
+                },
+                cooldowns,
+                Dashes::default(),
+            ))
+            .id();
+
+        TestApp { app, player }
+    }
+
+    /// Taps the dash key `millis` milliseconds after the start of the test
+    fn tap_at(&mut self, millis: u64) {
+        self.frame_at(millis, true);
+        self.frame_at(millis + 10, false);
+    }
+
+    fn frame_at(&mut self, millis: u64, pressed: bool) {
+        if pressed {
+            self.app.send_input(KeyCode::Space);
+        } else {
+            self.app.release_input(KeyCode::Space);
+        }
+
+        self.app.update_at(Duration::from_millis(millis));
+    }
+
+    fn dashes(&self) -> u32 {
+        self.app.world.get::<Dashes>(self.player).unwrap().0
+    }
+}
+
+#[test]
+fn cooldowns_tick_with_action_state() {
+    let mut app = TestApp::new(Cooldowns::new([(
+        Cooldown::new(Duration::from_millis(500)),
+        Ability::Dash,
+    )]));
+
+    app.tap_at(0);
+    assert_eq!(app.dashes(), 1);
+
+    app.tap_at(300);
+    assert_eq!(app.dashes(), 1);
+
+    app.tap_at(600);
+    assert_eq!(app.dashes(), 2);
+}
+
+#[test]
+fn charges_tick_with_action_state() {
+    let mut cooldowns =
+        Cooldowns::new([(Cooldown::new(Duration::from_millis(500)), Ability::Dash)]);
+    cooldowns.set_charges(Ability::Dash, Some(Charges::new(2)));
+    let mut app = TestApp::new(cooldowns);
+
+    app.tap_at(0);
+    app.tap_at(100);
+    app.tap_at(200);
+    assert_eq!(app.dashes(), 2);
+
+    // One charge has been replenished
+    app.tap_at(600);
+    app.tap_at(700);
+    assert_eq!(app.dashes(), 3);
+}
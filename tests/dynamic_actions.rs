@@ -0,0 +1,46 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::dynamic_action::DynamicAction;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Component)]
+struct Player;
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<DynamicAction>::default());
+    app
+}
+
+#[test]
+fn actions_registered_at_runtime_are_pressed() {
+    let mut app = test_app();
+    let base_jump = DynamicAction::register("base/jump").unwrap();
+
+    let player = app
+        .world
+        .spawn(InputManagerBundle {
+            input_map: InputMap::new([(KeyCode::Space, base_jump)]),
+            ..default()
+        })
+        .insert(Player)
+        .id();
+    app.update();
+
+    // A mod is loaded after the player was spawned
+    let cast_spell = DynamicAction::register("mod_x/cast_spell").unwrap();
+    app.world
+        .get_mut::<InputMap<DynamicAction>>(player)
+        .unwrap()
+        .insert(KeyCode::Q, cast_spell);
+
+    app.send_input(KeyCode::Q);
+    app.update();
+
+    let action_state = app.world.get::<ActionState<DynamicAction>>(player).unwrap();
+    assert!(action_state.pressed(cast_spell));
+    assert!(action_state.released(base_jump));
+    assert_eq!(action_state.get_pressed(), vec![cast_spell]);
+}
@@ -0,0 +1,117 @@
+use bevy::ecs::schedule::ShouldRun;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::prelude::*;
+
+const STEP: Duration = Duration::from_millis(20);
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Jump,
+}
+
+#[derive(StageLabel)]
+struct FixedStage;
+
+/// The number of fixed steps to run in the next frame
+#[derive(Resource, Default)]
+struct PendingSteps(u32);
+
+fn run_pending_steps(mut pending_steps: ResMut<PendingSteps>) -> ShouldRun {
+    if pending_steps.0 > 0 {
+        pending_steps.0 -= 1;
+        ShouldRun::YesAndCheckAgain
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// What the fixed steps have seen
+#[derive(Resource, Default)]
+struct Observations {
+    just_pressed: u32,
+    pressed: u32,
+    just_released: u32,
+    durations: Vec<Duration>,
+}
+
+fn observe(action_state: Res<ActionState<Action>>, mut observations: ResMut<Observations>) {
+    observations.just_pressed += action_state.just_pressed(Action::Jump) as u32;
+    observations.pressed += action_state.pressed(Action::Jump) as u32;
+    observations.just_released += action_state.just_released(Action::Jump) as u32;
+    observations
+        .durations
+        .push(action_state.current_duration(Action::Jump));
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_stage_after(
+            CoreStage::Update,
+            FixedStage,
+            SystemStage::parallel().with_run_criteria(run_pending_steps),
+        )
+        // The fixed stage must be added before the plugin
+        .add_plugin(InputManagerPlugin::<Action>::fixed_timestep(
+            FixedStage, STEP,
+        ))
+        .add_system_to_stage(FixedStage, observe)
+        .init_resource::<PendingSteps>()
+        .init_resource::<Observations>()
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::Space, Action::Jump)]));
+    app
+}
+
+#[test]
+fn presses_between_steps_are_seen_once() {
+    let mut app = test_app();
+
+    // Pressed and released before any fixed step runs
+    app.send_input(KeyCode::Space);
+    app.update();
+    app.release_input(KeyCode::Space);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .just_pressed(Action::Jump));
+
+    app.world.resource_mut::<PendingSteps>().0 = 2;
+    app.update();
+
+    let observations = app.world.resource::<Observations>();
+    assert_eq!(observations.just_pressed, 1);
+    assert_eq!(observations.pressed, 2);
+
+    // The release is seen by the next step
+    app.world.resource_mut::<PendingSteps>().0 = 3;
+    app.update();
+
+    let observations = app.world.resource::<Observations>();
+    assert_eq!(observations.just_pressed, 1);
+    assert_eq!(observations.pressed, 2);
+    assert_eq!(observations.just_released, 1);
+}
+
+#[test]
+fn durations_use_fixed_step_time() {
+    let mut app = test_app();
+
+    app.send_input(KeyCode::Space);
+    app.world.resource_mut::<PendingSteps>().0 = 3;
+    app.update();
+
+    // Durations are measured when ticking at the end of each step
+    let observations = app.world.resource::<Observations>();
+    assert_eq!(observations.durations, vec![Duration::ZERO, STEP, STEP * 2]);
+    assert_eq!(
+        app.world
+            .resource::<ActionState<Action>>()
+            .current_duration(Action::Jump),
+        STEP * 3
+    );
+}
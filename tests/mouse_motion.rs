@@ -1,10 +1,16 @@
 use bevy::input::mouse::MouseMotion;
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
-use leafwing_input_manager::axislike::{AxisType, DualAxisData, MouseMotionAxisType};
+use bevy::utils::Duration;
+use leafwing_input_manager::axislike::{
+    AxisType, DualAxisData, MouseMotionAxisType, MouseMotionNormalization,
+};
 use leafwing_input_manager::buttonlike::MouseMotionDirection;
 use leafwing_input_manager::prelude::*;
 
+mod common;
+use common::TimedApp;
+
 #[derive(Actionlike, Clone, Copy, Debug)]
 enum ButtonlikeTestAction {
     Up,
@@ -287,3 +293,65 @@ fn mouse_motion_virtualdpad() {
         DualAxisData::new(0.0, -1.0)
     );
 }
+
+#[test]
+fn mouse_motion_normalized_across_frame_rates() {
+    let mut app = TimedApp::new();
+    app.add_plugin(InputManagerPlugin::<AxislikeTestAction>::default())
+        .init_resource::<ActionState<AxislikeTestAction>>();
+
+    let mut input_map = InputMap::new([(SingleAxis::mouse_motion_x(), AxislikeTestAction::X)]);
+    input_map
+        .set_mouse_motion_normalization(MouseMotionNormalization::Stick { scale: 1.0 / 512.0 });
+    app.insert_resource(input_map);
+
+    let mut elapsed = Duration::ZERO;
+    app.update_at(elapsed);
+
+    // The mouse moves at 256 pixels per second, first at 32 and then at 128 frames per second
+    let second = Duration::from_secs(1);
+    for (frame_time, delta) in [(second / 32, 8.0), (second / 128, 2.0)] {
+        elapsed += frame_time;
+        app.send_input(SingleAxis::from_value(MouseMotionAxisType::X, delta));
+        app.update_at(elapsed);
+
+        let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+        assert_eq!(action_state.value(AxislikeTestAction::X), 0.5);
+    }
+
+    // Moving faster than 512 pixels per second saturates like a gamepad stick
+    elapsed += second / 128;
+    app.send_input(SingleAxis::from_value(MouseMotionAxisType::X, -20.0));
+    app.update_at(elapsed);
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(action_state.value(AxislikeTestAction::X), -1.0);
+}
+
+#[test]
+fn stick_like_mouse_motion_pairs_are_clamped_by_length() {
+    let mut app = TimedApp::new();
+    app.add_plugin(InputManagerPlugin::<AxislikeTestAction>::default())
+        .init_resource::<ActionState<AxislikeTestAction>>();
+
+    let mut input_map = InputMap::new([(DualAxis::mouse_motion(), AxislikeTestAction::XY)]);
+    input_map
+        .set_mouse_motion_normalization(MouseMotionNormalization::Stick { scale: 1.0 / 512.0 });
+    app.insert_resource(input_map);
+    app.update_at(Duration::ZERO);
+
+    // Moving diagonally at 1024 pixels per second along each axis saturates the pair, rather than each axis
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        8.0,
+        8.0,
+    ));
+    app.update_at(Duration::from_secs(1) / 128);
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    let axis_pair = action_state.axis_pair(AxislikeTestAction::XY).unwrap();
+    assert!((axis_pair.length() - 1.0).abs() < 1e-6);
+    assert_eq!(axis_pair.x(), axis_pair.y());
+    assert!((action_state.value(AxislikeTestAction::XY) - 1.0).abs() < 1e-6);
+}
@@ -0,0 +1,199 @@
+use bevy::ecs::schedule::ShouldRun;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use leafwing_input_manager::action_state::ActionEvent;
+use leafwing_input_manager::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Jump,
+}
+
+#[derive(Resource)]
+struct Paused(bool);
+
+fn unless_paused(paused: Res<Paused>) -> ShouldRun {
+    if paused.0 {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+fn test_app(plugin: InputManagerPlugin<Action>) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(plugin)
+        .insert_resource(Paused(false));
+
+    app.world.spawn(InputManagerBundle {
+        input_map: InputMap::new([(KeyCode::Space, Action::Jump)]),
+        ..default()
+    });
+    app
+}
+
+fn jump_pressed(app: &mut App) -> bool {
+    app.world
+        .query::<&ActionState<Action>>()
+        .single(&app.world)
+        .pressed(Action::Jump)
+}
+
+#[test]
+fn custom_stage() {
+    #[derive(StageLabel)]
+    struct InputStage;
+
+    let mut app = App::new();
+    app.add_stage_before(CoreStage::Update, InputStage, SystemStage::parallel());
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(InputPlugin)
+        .add_plugin(InputManagerPlugin::<Action>::default().in_stage(InputStage));
+    app.world.spawn(InputManagerBundle {
+        input_map: InputMap::new([(KeyCode::Space, Action::Jump)]),
+        ..default()
+    });
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(jump_pressed(&mut app));
+}
+
+#[test]
+fn run_criteria() {
+    let mut app = test_app(InputManagerPlugin::default().run_if(unless_paused));
+
+    app.world.resource_mut::<Paused>().0 = true;
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(!jump_pressed(&mut app));
+
+    app.world.resource_mut::<Paused>().0 = false;
+    app.update();
+    assert!(jump_pressed(&mut app));
+
+    // `ToggleActions` is still respected
+    app.world.resource_mut::<ToggleActions<Action>>().enabled = false;
+    app.update();
+    assert!(!jump_pressed(&mut app));
+}
+
+#[test]
+fn without_release_on_input_map_removed() {
+    let mut app = test_app(InputManagerPlugin::default().without_release_on_input_map_removed());
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(jump_pressed(&mut app));
+
+    let entity = app
+        .world
+        .query_filtered::<Entity, With<InputMap<Action>>>()
+        .single(&app.world);
+    app.world.entity_mut(entity).remove::<InputMap<Action>>();
+    app.update();
+    assert!(jump_pressed(&mut app));
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum AppState {
+    Menu,
+    Playing,
+}
+
+#[test]
+fn active_in_state() {
+    let mut app = test_app(InputManagerPlugin::default().active_in_state(AppState::Menu));
+    app.add_state(AppState::Menu);
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    assert!(jump_pressed(&mut app));
+
+    // State transitions are applied during `CoreStage::Update`, so actions are released the next frame
+    app.world
+        .resource_mut::<State<AppState>>()
+        .set(AppState::Playing)
+        .unwrap();
+    app.update();
+    assert!(jump_pressed(&mut app));
+    app.update();
+    assert!(!app.world.resource::<ToggleActions<Action>>().enabled);
+    assert!(!jump_pressed(&mut app));
+
+    app.world
+        .resource_mut::<State<AppState>>()
+        .set(AppState::Menu)
+        .unwrap();
+    app.update();
+    app.update();
+    assert!(app.world.resource::<ToggleActions<Action>>().enabled);
+    assert!(jump_pressed(&mut app));
+}
+
+#[test]
+fn with_action_events() {
+    let mut app = test_app(InputManagerPlugin::default().with_action_events());
+    let player = app
+        .world
+        .query_filtered::<Entity, With<ActionState<Action>>>()
+        .single(&app.world);
+    let mut reader = app
+        .world
+        .resource::<Events<ActionEvent<Action>>>()
+        .get_reader();
+
+    app.send_input(KeyCode::Space);
+    app.update();
+    let events: Vec<_> = reader
+        .iter(app.world.resource::<Events<ActionEvent<Action>>>())
+        .cloned()
+        .collect();
+    assert!(matches!(
+        events[..],
+        [
+            ActionEvent::Pressed {
+                entity: Some(entity),
+                action: Action::Jump,
+            },
+            ActionEvent::ValueChanged {
+                action: Action::Jump,
+                value,
+                ..
+            },
+        ] if entity == player && value == 1.0
+    ));
+
+    app.update();
+    let events: Vec<_> = reader
+        .iter(app.world.resource::<Events<ActionEvent<Action>>>())
+        .cloned()
+        .collect();
+    assert!(matches!(
+        events[..],
+        [ActionEvent::Held {
+            action: Action::Jump,
+            ..
+        }]
+    ));
+
+    app.release_input(KeyCode::Space);
+    app.update();
+    let events: Vec<_> = reader
+        .iter(app.world.resource::<Events<ActionEvent<Action>>>())
+        .cloned()
+        .collect();
+    assert!(matches!(
+        events[..],
+        [
+            ActionEvent::Released {
+                entity: Some(entity),
+                action: Action::Jump,
+                ..
+            },
+            ActionEvent::ValueChanged { value, .. },
+        ] if entity == player && value == 0.0
+    ));
+}
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::user_input::TriggerMode;
+
+mod common;
+use common::TimedApp;
+
+#[derive(Actionlike, Clone, Copy, Debug)]
+enum Action {
+    Reload,
+    WeaponWheel,
+}
+
+const MAX_CLICK: Duration = Duration::from_millis(250);
+
+/// Opening the weapon wheel cancels the reload
+fn open_weapon_wheel(mut action_state: ResMut<ActionState<Action>>) {
+    if action_state.current_duration(Action::WeaponWheel) > MAX_CLICK {
+        action_state.consume(Action::Reload);
+    }
+}
+
+/// Holds the reload key, which also opens the weapon wheel
+struct TestApp {
+    app: TimedApp,
+}
+
+impl TestApp {
+    fn new(trigger: TriggerMode, weapon_wheel: bool) -> Self {
+        let mut app = TimedApp::new();
+        app.add_plugin(InputManagerPlugin::<Action>::default())
+            .init_resource::<ActionState<Action>>();
+
+        if weapon_wheel {
+            app.add_system(open_weapon_wheel);
+        }
+
+        let mut input_map = InputMap::default();
+        input_map
+            .insert(KeyCode::R, Action::WeaponWheel)
+            .insert_with_trigger(KeyCode::R, Action::Reload, trigger);
+        app.insert_resource(input_map);
+
+        TestApp { app }
+    }
+
+    /// Runs a frame `millis` milliseconds after the start of the test
+    fn frame_at(&mut self, millis: u64) {
+        self.app.update_at(Duration::from_millis(millis));
+    }
+
+    /// Holds the key from `from` until `until` milliseconds after the start of the test,
+    /// returning whether reloading was just pressed once it was released
+    fn hold(&mut self, from: u64, until: u64) -> bool {
+        self.app.send_input(KeyCode::R);
+        for millis in (from..=until).step_by(50) {
+            self.frame_at(millis);
+            assert!(
+                self.action_state().released(Action::Reload),
+                "reloading should wait for the key to be released"
+            );
+        }
+
+        self.app.release_input(KeyCode::R);
+        self.frame_at(until + 10);
+        self.action_state().just_pressed(Action::Reload)
+    }
+
+    fn action_state(&self) -> &ActionState<Action> {
+        self.app.world.resource::<ActionState<Action>>()
+    }
+}
+
+#[test]
+fn click() {
+    let click = TriggerMode::Click {
+        max_duration: MAX_CLICK,
+    };
+
+    let mut app = TestApp::new(click, false);
+    assert!(app.hold(0, 100));
+
+    // Clicks press the action for a single frame
+    app.frame_at(200);
+    assert!(app.action_state().released(Action::Reload));
+
+    // Holding for too long is not a click
+    assert!(!app.hold(300, 600));
+}
+
+#[test]
+fn release_unless_consumed() {
+    let mut app = TestApp::new(TriggerMode::Release, true);
+    assert!(app.hold(0, 200));
+
+    // The weapon wheel was opened, so the release does not reload
+    app.frame_at(300);
+    assert!(!app.hold(400, 1000));
+
+    // Later releases are unaffected
+    app.frame_at(1100);
+    assert!(app.hold(1200, 1300));
+}
+
+#[test]
+fn consuming_once_cancels_release() {
+    let mut app = TestApp::new(TriggerMode::Release, false);
+
+    app.app.send_input(KeyCode::R);
+    app.frame_at(0);
+    app.app
+        .world
+        .resource_mut::<ActionState<Action>>()
+        .consume(Action::Reload);
+
+    // The binding is still held for several frames after being consumed
+    app.frame_at(50);
+    app.frame_at(100);
+    app.app.release_input(KeyCode::R);
+    app.frame_at(110);
+    assert!(!app.action_state().just_pressed(Action::Reload));
+
+    // Later releases are unaffected
+    app.frame_at(200);
+    assert!(app.hold(300, 400));
+}